@@ -3,56 +3,142 @@ use std::boxed::Box;
 use core::{
     cell::UnsafeCell,
     marker::PhantomData,
+    mem::ManuallyDrop,
     ops::Deref,
     ptr::NonNull,
-    sync::atomic::{AtomicUsize, Ordering},
+    sync::atomic::{fence, AtomicUsize, Ordering},
 };
 
+// the counts are stored off by one, so the strong count wraps around to this once the last strong is dropped
+const DEAD: usize = usize::MAX;
+
 pub struct Athin<T: ?Sized> {
     inner: NonNull<AthinInner<T>>,
     drop: PhantomData<T>,
 }
 
+pub struct AthinWeak<T: ?Sized> {
+    inner: NonNull<AthinInner<T>>,
+    drop: PhantomData<T>,
+}
+
 unsafe impl<T: Send + Sync + ?Sized> Send for Athin<T> {}
 unsafe impl<T: Send + Sync + ?Sized> Sync for Athin<T> {}
+unsafe impl<T: Send + Sync + ?Sized> Send for AthinWeak<T> {}
+unsafe impl<T: Send + Sync + ?Sized> Sync for AthinWeak<T> {}
 
 #[repr(C)]
 pub struct AthinInner<T: ?Sized> {
     count: AtomicUsize,
-    value: T,
+    // all strong pointers collectively hold a single weak pointer
+    weak: AtomicUsize,
+    value: ManuallyDrop<T>,
 }
 
 impl<T> AthinInner<T> {
     pub fn new(value: T) -> Self {
         Self {
             count: AtomicUsize::new(0),
-            value,
+            weak: AtomicUsize::new(0),
+            value: ManuallyDrop::new(value),
         }
     }
 }
 
-impl Athin<AtomicUsize> {
-    pub(crate) fn dangling() -> Self {
-        static mut VALUE: UnsafeCell<AthinInner<AtomicUsize>> = UnsafeCell::new(AthinInner {
-            count: AtomicUsize::new(1),
-            value: AtomicUsize::new(0),
-        });
+// only runs for an inner that never became an `Athin`, `drop_weak` frees the allocation without it
+impl<T: ?Sized> Drop for AthinInner<T> {
+    fn drop(&mut self) { unsafe { ManuallyDrop::drop(&mut self.value) } }
+}
+
+#[cold]
+#[inline(never)]
+fn abort() -> ! {
+    struct Abort;
 
-        static DANGLING: Athin<AtomicUsize> = Athin {
-            drop: PhantomData,
-            inner: unsafe { NonNull::new_unchecked(VALUE.get()) },
-        };
+    impl Drop for Abort {
+        fn drop(&mut self) { panic!() }
+    }
 
-        DANGLING.clone()
+    let _abort = Abort;
+    panic!();
+}
+
+fn increment(count: &AtomicUsize) {
+    if count.fetch_add(1, Ordering::Relaxed) >= (isize::MAX as usize) {
+        abort()
     }
 }
 
+unsafe fn drop_weak<T: ?Sized>(inner: NonNull<AthinInner<T>>) {
+    if inner.as_ref().weak.fetch_sub(1, Ordering::Release) == 0 {
+        fence(Ordering::Acquire);
+        // the value was already dropped along with the last strong pointer
+        drop(Box::from_raw(inner.as_ptr() as *mut ManuallyDrop<AthinInner<T>>));
+    }
+}
+
+macro_rules! dangling {
+    ($type:ty, $value:expr) => {
+        impl Athin<$type> {
+            pub(crate) fn dangling() -> Self {
+                static mut VALUE: UnsafeCell<AthinInner<$type>> = UnsafeCell::new(AthinInner {
+                    count: AtomicUsize::new(1),
+                    weak: AtomicUsize::new(1),
+                    value: ManuallyDrop::new($value),
+                });
+
+                static DANGLING: Athin<$type> = Athin {
+                    drop: PhantomData,
+                    inner: unsafe { NonNull::new_unchecked(UnsafeCell::raw_get(core::ptr::addr_of!(VALUE))) },
+                };
+
+                DANGLING.clone()
+            }
+        }
+    };
+}
+
+dangling!(AtomicUsize, AtomicUsize::new(0));
+dangling!([AtomicUsize; 2], [AtomicUsize::new(0), AtomicUsize::new(0)]);
+
 impl<T> Athin<T> {
     pub fn new(value: T) -> Self { Box::new(AthinInner::new(value)).into() }
 }
 
 impl<T: ?Sized> Athin<T> {
     pub fn strong_count(&self) -> usize { unsafe { self.inner.as_ref().count.load(Ordering::Acquire) } }
+
+    pub fn downgrade(this: &Self) -> AthinWeak<T> {
+        unsafe { increment(&this.inner.as_ref().weak) }
+
+        AthinWeak {
+            inner: this.inner,
+            drop: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized> AthinWeak<T> {
+    pub fn as_ptr(&self) -> *const T { unsafe { core::ptr::addr_of!((*self.inner.as_ptr()).value) as *const T } }
+
+    pub fn is_dangling(&self) -> bool { unsafe { self.inner.as_ref().count.load(Ordering::Acquire) == DEAD } }
+
+    pub fn upgrade(&self) -> Option<Athin<T>> {
+        let count = unsafe { &self.inner.as_ref().count };
+
+        count
+            .fetch_update(Ordering::Acquire, Ordering::Relaxed, |count| match count {
+                DEAD => None,
+                count if count >= (isize::MAX as usize) => abort(),
+                count => Some(count + 1),
+            })
+            .ok()?;
+
+        Some(Athin {
+            inner: self.inner,
+            drop: PhantomData,
+        })
+    }
 }
 
 impl<T: ?Sized> From<Box<AthinInner<T>>> for Athin<T> {
@@ -67,18 +153,18 @@ impl<T: ?Sized> From<Box<AthinInner<T>>> for Athin<T> {
 
 impl<T: ?Sized> Clone for Athin<T> {
     fn clone(&self) -> Self {
-        let count = unsafe { self.inner.as_ref().count.fetch_add(1, Ordering::Relaxed) };
-
-        if count >= (isize::MAX as usize) {
-            struct Abort;
+        unsafe { increment(&self.inner.as_ref().count) }
 
-            impl Drop for Abort {
-                fn drop(&mut self) { panic!() }
-            }
-
-            let _abort = Abort;
-            panic!();
+        Self {
+            inner: self.inner,
+            drop: PhantomData,
         }
+    }
+}
+
+impl<T: ?Sized> Clone for AthinWeak<T> {
+    fn clone(&self) -> Self {
+        unsafe { increment(&self.inner.as_ref().weak) }
 
         Self {
             inner: self.inner,
@@ -92,12 +178,18 @@ impl<T: ?Sized> Drop for Athin<T> {
         let count = unsafe { self.inner.as_ref().count.fetch_sub(1, Ordering::Release) };
         if count == 0 {
             unsafe {
-                Box::from_raw(self.inner.as_ptr());
+                fence(Ordering::Acquire);
+                ManuallyDrop::drop(&mut (*self.inner.as_ptr()).value);
+                drop_weak(self.inner);
             }
         }
     }
 }
 
+impl<T: ?Sized> Drop for AthinWeak<T> {
+    fn drop(&mut self) { unsafe { drop_weak(self.inner) } }
+}
+
 impl<T: ?Sized> Deref for Athin<T> {
     type Target = T;
 
@@ -6,17 +6,28 @@ mod writer;
 
 use raw_buffers::RawBuffers;
 
+use core::sync::atomic::{AtomicUsize, Ordering};
 use radium::Radium;
-pub use reader::{Reader, ReaderGuard};
-pub use writer::{Split, SplitMut, Swap, Writer};
+#[cfg(feature = "futures")]
+pub use reader::Changes;
+pub use reader::{OwnedReaderGuard, Reader, ReaderGuard};
+pub use writer::{Split, SplitMut, Swap, SwapFuture, Writer};
 
+// `repr(C)` so that `Inner<[B; 0], S>` is a prefix of `Inner<[B], S>`, see `from_slice_snapshot`
+#[repr(C)]
 pub struct Inner<R: ?Sized, S, W = <S as Strategy>::Which> {
-    which: W,
+    pub(crate) which: W,
+    generation: AtomicUsize,
+    #[cfg(feature = "futures")]
+    wakers: parking_lot::Mutex<std::vec::Vec<core::task::Waker>>,
     pub strategy: S,
-    raw: RawBuffers<R>,
+    pub(crate) raw: RawBuffers<R>,
 }
 
-pub fn new<I: RawParts>(inner: I) -> (Writer<I::Strong>, Reader<I::Weak>) {
+/// The writer and reader returned by `new`
+pub type Pair<I> = (Writer<<I as RawParts>::Strong>, Reader<<I as RawParts>::Weak>);
+
+pub fn new<I: RawParts>(inner: I) -> Pair<I> {
     let (writer, reader) = inner.raw_parts();
     let strategy = &writer.strategy;
     unsafe {
@@ -31,14 +42,147 @@ pub fn new<I: RawParts>(inner: I) -> (Writer<I::Strong>, Reader<I::Weak>) {
 
 impl<S: Strategy, B> Inner<[B; 2], S> {
     pub fn new(strategy: S, front: B, back: B) -> Self { Self::from_raw_parts(strategy, [front, back]) }
+
+    pub fn from_snapshot(strategy: S, snapshot: B) -> Self
+    where
+        B: Clone,
+    {
+        Self::new(strategy, snapshot.clone(), snapshot)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: Strategy, B> Inner<crate::raw::SliceBuffers<B>, S> {
+    pub fn from_slices(strategy: S, front: std::vec::Vec<B>, back: std::vec::Vec<B>) -> Self {
+        Self::from_raw_parts(strategy, crate::raw::SliceBuffers::new(front, back))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: Strategy, B: Clone> Inner<[B], S> {
+    pub fn from_slice_snapshot(strategy: S, snapshot: &[B]) -> std::boxed::Box<Self> {
+        use std::alloc::{alloc, handle_alloc_error, Layout};
+
+        let len = snapshot.len().checked_mul(2).expect("capacity overflow");
+
+        // the buffers start at `raw`, which may be before the end of `Inner<[B; 0], S>`
+        // if it has trailing padding, this is the same layout that `Box` frees with
+        let offset = core::mem::offset_of!(Inner<[B; 0], S>, raw);
+        let layout = core::mem::size_of::<B>()
+            .checked_mul(len)
+            .and_then(|buffers| buffers.checked_add(offset))
+            .and_then(|size| Layout::from_size_align(size, core::mem::align_of::<Inner<[B; 0], S>>()).ok())
+            .expect("capacity overflow")
+            .pad_to_align();
+
+        unsafe {
+            // `Inner` always contains an `AtomicUsize`, so the layout isn't zero-sized
+            let ptr = alloc(layout);
+            if ptr.is_null() {
+                handle_alloc_error(layout)
+            }
+
+            let ptr = core::ptr::slice_from_raw_parts_mut(ptr.cast::<B>(), len) as *mut Self;
+
+            // if cloning panics the allocation is leaked, which is safe
+            ptr.cast::<Inner<[B; 0], S>>()
+                .write(Inner::from_raw_unchecked(strategy, []));
+            let buffers = core::ptr::addr_of_mut!((*ptr).raw).cast::<B>();
+            for (i, value) in snapshot.iter().chain(snapshot).enumerate() {
+                buffers.add(i).write(value.clone());
+            }
+
+            std::boxed::Box::from_raw(ptr)
+        }
+    }
 }
 
 impl<S: Strategy, R: RawDoubleBuffer> Inner<R, S> {
-    pub fn from_raw_parts(strategy: S, buffers: R) -> Self {
+    pub fn from_raw_parts(strategy: S, buffers: R) -> Self { Self::from_raw_unchecked(strategy, buffers) }
+}
+
+impl<S: Strategy, R> Inner<R, S> {
+    fn from_raw_unchecked(strategy: S, buffers: R) -> Self {
         Self {
             strategy,
             which: Radium::new(false),
+            generation: AtomicUsize::new(0),
+            #[cfg(feature = "futures")]
+            wakers: parking_lot::Mutex::new(std::vec::Vec::new()),
             raw: RawBuffers::new(buffers),
         }
     }
 }
+
+// the low bit of `generation` is set while readers may be parked on it
+#[cfg(feature = "std")]
+const PARKED_READERS: usize = 1;
+const GENERATION_STEP: usize = 2;
+
+impl<R: ?Sized, S, W> Inner<R, S, W> {
+    pub(crate) fn generation(&self) -> usize { self.generation.load(Ordering::Acquire) / GENERATION_STEP }
+
+    fn bump_generation(&self) {
+        let generation = self.generation.fetch_add(GENERATION_STEP, Ordering::AcqRel);
+
+        #[cfg(feature = "std")]
+        if generation & PARKED_READERS != 0 {
+            self.generation.fetch_and(!PARKED_READERS, Ordering::Relaxed);
+            unsafe { parking_lot_core::unpark_all(self.generation_key(), parking_lot_core::DEFAULT_UNPARK_TOKEN) };
+            #[cfg(feature = "futures")]
+            self.wake_readers();
+        }
+
+        #[cfg(not(feature = "std"))]
+        let _ = generation;
+    }
+
+    #[cfg(feature = "std")]
+    fn generation_key(&self) -> usize { &self.generation as *const AtomicUsize as usize }
+
+    #[cfg(feature = "std")]
+    fn park_until_swap(&self, seen: usize, deadline: std::time::Instant) {
+        let validate = || {
+            let generation = self.generation.fetch_or(PARKED_READERS, Ordering::Acquire);
+            generation / GENERATION_STEP == seen
+        };
+
+        unsafe {
+            parking_lot_core::park(
+                self.generation_key(),
+                validate,
+                || (),
+                |_, _| (),
+                parking_lot_core::DEFAULT_PARK_TOKEN,
+                Some(deadline),
+            );
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<R: ?Sized, S, W> Inner<R, S, W> {
+    // returns true if the generation has already moved past `seen`
+    fn register_reader_waker(&self, seen: usize, waker: &core::task::Waker) -> bool {
+        {
+            let mut wakers = self.wakers.lock();
+            if !wakers.iter().any(|old| old.will_wake(waker)) {
+                wakers.push(waker.clone());
+            }
+        }
+
+        let generation = self.generation.fetch_or(PARKED_READERS, Ordering::AcqRel);
+        generation / GENERATION_STEP != seen
+    }
+
+    fn wake_readers(&self) {
+        let wakers = core::mem::take(&mut *self.wakers.lock());
+        wakers.into_iter().for_each(core::task::Waker::wake)
+    }
+}
+
+// wake up any streams so they can see that the writer is gone
+#[cfg(feature = "futures")]
+impl<R: ?Sized, S, W> Drop for Inner<R, S, W> {
+    fn drop(&mut self) { self.wake_readers() }
+}
@@ -20,6 +20,7 @@ impl<R: ?Sized + RawDoubleBuffer> RawBuffers<R> {
         (&*writer, &*reader)
     }
 
+    #[allow(clippy::mut_from_ref)]
     pub(crate) unsafe fn split_mut(&self, which: bool) -> (&mut R::Buffer, &R::Buffer) {
         let (writer, reader) = RawDoubleBuffer::split(self.0.get(), which);
         (&mut *writer, &*reader)
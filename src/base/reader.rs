@@ -2,7 +2,10 @@ use radium::Radium;
 
 use core::{marker::PhantomData, mem::ManuallyDrop, ops::Deref, sync::atomic::Ordering};
 
-use crate::traits::{Buffer, ReaderTagW, Strategy, StrongBuffer, WeakBuffer};
+use crate::traits::{Buffer, ReaderTagW, SharedStrategy, Strategy, StrongBuffer, WeakBuffer};
+
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
 
 impl<I> Default for Reader<I>
 where
@@ -14,6 +17,7 @@ where
         let tag = unsafe { strategy.reader_tag() };
         Self {
             tag,
+            seen: 0,
             inner: I::default(),
         }
     }
@@ -21,6 +25,7 @@ where
 
 pub struct Reader<I, T = ReaderTagW<I>> {
     tag: T,
+    seen: usize,
     inner: I,
 }
 
@@ -29,24 +34,60 @@ pub struct ReaderGuard<'reader, I: StrongBuffer, T: ?Sized = Buffer<I>> {
     raw: RawGuard<'reader, I>,
 }
 
+// fields are dropped in order, so the guard ends before the reader is dropped
+pub struct OwnedReaderGuard<I: WeakBuffer, T: ?Sized = Buffer<<I as WeakBuffer>::Strong>> {
+    value: *const T,
+    raw: RawGuard<'static, I::Strong>,
+    reader: Reader<I>,
+}
+
+unsafe impl<I: WeakBuffer, T: ?Sized + Sync> Send for OwnedReaderGuard<I, T>
+where
+    Reader<I>: Send,
+    I::Strong: Send,
+    crate::traits::RawGuard<I::Strong>: Send,
+{
+}
+
+unsafe impl<I: WeakBuffer, T: ?Sized + Sync> Sync for OwnedReaderGuard<I, T>
+where
+    Reader<I>: Sync,
+    I::Strong: Sync,
+    crate::traits::RawGuard<I::Strong>: Sync,
+{
+}
+
 struct RawGuard<'reader, I: StrongBuffer> {
     reader: PhantomData<&'reader ()>,
+    #[cfg(feature = "tracing")]
+    id: Option<usize>,
     raw: ManuallyDrop<crate::traits::RawGuard<I>>,
     keep_alive: I,
 }
 
+type BeginGuard<'reader, I> = (
+    RawGuard<'reader, <I as WeakBuffer>::Strong>,
+    *const Buffer<<I as WeakBuffer>::Strong>,
+);
+
 impl<I: StrongBuffer> Drop for RawGuard<'_, I> {
-    fn drop(&mut self) { unsafe { self.keep_alive.strategy.end_guard(ManuallyDrop::take(&mut self.raw)) } }
+    fn drop(&mut self) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(reader = ?self.id, "reader guard released");
+
+        unsafe { self.keep_alive.strategy.end_guard(ManuallyDrop::take(&mut self.raw)) }
+    }
 }
 
 impl<I, T> Reader<I, T> {
-    pub(crate) unsafe fn from_raw_parts(inner: I, tag: T) -> Self { Self { tag, inner } }
+    pub(crate) unsafe fn from_raw_parts(inner: I, tag: T) -> Self { Self { tag, seen: 0, inner } }
 }
 
 impl<I: WeakBuffer> Reader<I> {
     pub(crate) fn new(inner: &I::Strong) -> Self {
         Self {
             tag: unsafe { inner.strategy.reader_tag() },
+            seen: inner.generation(),
             inner: inner.downgrade(),
         }
     }
@@ -54,31 +95,184 @@ impl<I: WeakBuffer> Reader<I> {
     #[inline]
     pub fn is_dangling(&self) -> bool { self.inner.is_dangling() }
 
+    pub(crate) fn as_weak(&self) -> &I { &self.inner }
+
+    /// The current swap generation, or the last one this reader saw if the writer is gone
+    pub fn generation(&self) -> usize {
+        match self.inner.upgrade() {
+            Ok(inner) => inner.generation(),
+            Err(_) => self.seen,
+        }
+    }
+
+    /// Has the writer swapped since the last call to `get`/`try_get`
+    pub fn has_changed(&self) -> bool { self.generation() != self.seen }
+
+    /// Block until the writer swaps after the last call to `get`/`try_get`,
+    /// returns false if the timeout expired or the writer is gone
+    #[cfg(feature = "std")]
+    pub fn wait_for_swap(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let inner = match self.inner.upgrade() {
+                Ok(inner) => inner,
+                Err(_) => return false,
+            };
+
+            if inner.generation() != self.seen {
+                return true
+            }
+
+            if Instant::now() >= deadline {
+                return false
+            }
+
+            inner.park_until_swap(self.seen, deadline);
+        }
+    }
+
     #[inline]
     pub fn get(&mut self) -> ReaderGuard<'_, I::Strong> {
-        self.try_get().expect("Tried to reader from a dangling `Reader<B>`")
+        self.try_get().expect("Tried to read from a dangling `Reader<B>`")
     }
 
     #[inline]
     pub fn try_get(&mut self) -> Result<ReaderGuard<'_, I::Strong>, I::UpgradeError> {
+        let (raw, buffer) = self.begin_guard()?;
+
+        Ok(ReaderGuard {
+            value: unsafe { &*buffer },
+            raw,
+        })
+    }
+
+    /// Like `get`, but the guard owns the reader, so it isn't tied to a borrow
+    #[inline]
+    pub fn get_owned(self) -> OwnedReaderGuard<I> {
+        match self.try_get_owned() {
+            Ok(guard) => guard,
+            Err(_) => panic!("Tried to read from a dangling `Reader<B>`"),
+        }
+    }
+
+    /// Like `try_get`, but the guard owns the reader, so it isn't tied to a borrow
+    #[inline]
+    pub fn try_get_owned(mut self) -> Result<OwnedReaderGuard<I>, (Self, I::UpgradeError)> {
+        match self.begin_guard() {
+            Ok((raw, value)) => Ok(OwnedReaderGuard {
+                value,
+                raw,
+                reader: self,
+            }),
+            Err(err) => Err((self, err)),
+        }
+    }
+
+    #[inline]
+    fn begin_guard<'a>(&mut self) -> Result<BeginGuard<'a, I>, I::UpgradeError> {
         let keep_alive = self.inner.upgrade()?;
-        let inner = &*keep_alive;
-        let guard = inner.strategy.begin_guard(&mut self.tag);
+        let guard = keep_alive.strategy.begin_guard(&mut self.tag);
+
+        // load the generation before `which` so that a concurrent swap
+        // can only cause a spurious change notification, not a missed one
+        self.seen = keep_alive.generation();
+
+        Ok(self.read_buffer(keep_alive, guard))
+    }
 
+    #[inline]
+    fn read_buffer<'a>(&self, keep_alive: I::Strong, guard: crate::traits::RawGuard<I::Strong>) -> BeginGuard<'a, I> {
+        let inner = &*keep_alive;
         let which = inner.which.load(Ordering::Acquire);
         let buffer = unsafe { inner.raw.read(which) };
 
-        Ok(ReaderGuard {
-            value: unsafe { &*buffer },
-            raw: RawGuard {
+        #[cfg(feature = "tracing")]
+        let id = inner.strategy.reader_id(&self.tag);
+        #[cfg(feature = "tracing")]
+        tracing::debug!(reader = ?id, generation = inner.generation(), which, "reader guard acquired");
+
+        (
+            RawGuard {
                 reader: PhantomData,
+                #[cfg(feature = "tracing")]
+                id,
                 raw: ManuallyDrop::new(guard),
                 keep_alive,
             },
+            buffer,
+        )
+    }
+}
+
+impl<I: WeakBuffer> Reader<I>
+where
+    I::Strategy: SharedStrategy,
+{
+    /// Like `get`, but any number of these guards can be alive at once,
+    /// doesn't change what `has_changed` compares against
+    #[inline]
+    pub fn get_shared(&self) -> ReaderGuard<'_, I::Strong> {
+        self.try_get_shared().expect("Tried to read from a dangling `Reader<B>`")
+    }
+
+    #[inline]
+    pub fn try_get_shared(&self) -> Result<ReaderGuard<'_, I::Strong>, I::UpgradeError> {
+        let keep_alive = self.inner.upgrade()?;
+        let guard = keep_alive.strategy.begin_shared_guard(&self.tag);
+        let (raw, buffer) = self.read_buffer(keep_alive, guard);
+
+        Ok(ReaderGuard {
+            value: unsafe { &*buffer },
+            raw,
         })
     }
 }
 
+#[cfg(feature = "futures")]
+pub struct Changes<'reader, I: WeakBuffer> {
+    reader: &'reader mut Reader<I>,
+}
+
+#[cfg(feature = "futures")]
+impl<I: WeakBuffer> Reader<I> {
+    /// A stream of snapshots, yields the latest buffer whenever the writer has swapped since the
+    /// reader last saw it. Swaps that happen before the stream is polled are merged into one item,
+    /// ends once the writer is gone
+    pub fn changes(&mut self) -> Changes<'_, I> { Changes { reader: self } }
+}
+
+#[cfg(feature = "futures")]
+impl<I: WeakBuffer> Unpin for Changes<'_, I> {}
+
+#[cfg(feature = "futures")]
+impl<I: WeakBuffer> futures_core::Stream for Changes<'_, I>
+where
+    Buffer<I::Strong>: Clone,
+{
+    type Item = Buffer<I::Strong>;
+
+    fn poll_next(self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Option<Self::Item>> {
+        use core::task::Poll;
+
+        let reader = &mut *self.get_mut().reader;
+
+        let changed = match reader.inner.upgrade() {
+            Err(_) => return Poll::Ready(None),
+            Ok(inner) => inner.generation() != reader.seen || inner.register_reader_waker(reader.seen, cx.waker()),
+        };
+
+        if !changed {
+            return Poll::Pending
+        }
+
+        match reader.try_get() {
+            Ok(guard) => Poll::Ready(Some(Buffer::<I::Strong>::clone(&guard))),
+            Err(_) => Poll::Ready(None),
+        }
+    }
+}
+
 impl<I: WeakBuffer> Clone for Reader<I> {
     fn clone(&self) -> Self {
         let tag = match self.inner.upgrade() {
@@ -88,6 +282,7 @@ impl<I: WeakBuffer> Clone for Reader<I> {
 
         Reader {
             inner: self.inner.clone(),
+            seen: self.seen,
             tag,
         }
     }
@@ -121,3 +316,42 @@ impl<'reader, I: StrongBuffer, T> ReaderGuard<'reader, I, T> {
         }
     }
 }
+
+impl<I: WeakBuffer, T: ?Sized> Deref for OwnedReaderGuard<I, T> {
+    type Target = T;
+
+    // the buffer is kept alive by `raw.keep_alive`, which derefs to a stable address
+    fn deref(&self) -> &Self::Target { unsafe { &*self.value } }
+}
+
+impl<I: WeakBuffer, T: ?Sized> OwnedReaderGuard<I, T> {
+    pub fn strategy(this: &Self) -> &I::Strategy { &this.raw.keep_alive.strategy }
+
+    pub fn reader(this: &Self) -> &Reader<I> { &this.reader }
+
+    /// End the guard and give back the reader
+    pub fn into_reader(this: Self) -> Reader<I> {
+        let Self { raw, reader, .. } = this;
+        drop(raw);
+        reader
+    }
+
+    pub fn map<F: FnOnce(&T) -> &U, U: ?Sized>(this: Self, f: F) -> OwnedReaderGuard<I, U> {
+        OwnedReaderGuard {
+            value: f(unsafe { &*this.value }),
+            raw: this.raw,
+            reader: this.reader,
+        }
+    }
+
+    pub fn try_map<F: FnOnce(&T) -> Option<&U>, U: ?Sized>(this: Self, f: F) -> Result<OwnedReaderGuard<I, U>, Self> {
+        match f(unsafe { &*this.value }) {
+            None => Err(this),
+            Some(value) => Ok(OwnedReaderGuard {
+                value,
+                raw: this.raw,
+                reader: this.reader,
+            }),
+        }
+    }
+}
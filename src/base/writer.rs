@@ -1,6 +1,14 @@
 use radium::Radium;
 
-use core::sync::atomic::Ordering;
+use core::{
+    future::Future,
+    pin::Pin,
+    sync::atomic::Ordering,
+    task::{Context, Poll},
+};
+
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
 
 use crate::traits::{Buffer, Capture, CaptureError, Strategy, StrongBuffer, TrustedRadium};
 
@@ -23,6 +31,11 @@ pub struct SplitMut<'a, B: ?Sized> {
     pub reader: &'a B,
 }
 
+pub struct SwapFuture<'a, I: StrongBuffer> {
+    writer: &'a Writer<I>,
+    swap: Option<Swap<Capture<I>>>,
+}
+
 struct FinishSwapOnDrop<'a, S: Strategy> {
     strategy: &'a S,
     swap: &'a mut S::Capture,
@@ -31,11 +44,20 @@ struct FinishSwapOnDrop<'a, S: Strategy> {
 impl<S: Strategy> Drop for FinishSwapOnDrop<'_, S> {
     fn drop(&mut self) {
         while !self.strategy.readers_have_exited(self.swap) {
-            self.strategy.pause(&mut self.swap)
+            self.strategy.pause(self.swap)
         }
     }
 }
 
+// a parked writer wakes up regularly to recheck the readers, so only log the first park of each swap
+#[cfg(feature = "tracing")]
+fn trace_parked<S: Strategy>(strategy: &S, capture: &S::Capture, parked: &mut bool) {
+    if !*parked && strategy.last_pause(capture) == crate::traits::PauseKind::Park {
+        *parked = true;
+        tracing::debug!(active_readers = ?strategy.active_readers(capture), "writer parked waiting for readers");
+    }
+}
+
 impl<I, T> Writer<I, T> {
     pub(crate) unsafe fn from_raw_parts(inner: I, tag: T) -> Self { Self { tag, inner } }
 }
@@ -45,8 +67,19 @@ impl<I: StrongBuffer> Writer<I> {
 
     pub fn strategy(&self) -> &I::Strategy { &self.inner.strategy }
 
+    pub fn generation(&self) -> usize { self.inner.generation() }
+
     pub fn get(&self) -> &Buffer<I> { self.split().writer }
 
+    #[cfg(all(feature = "serde", feature = "alloc"))]
+    pub fn snapshot(&self) -> <Buffer<I> as std::borrow::ToOwned>::Owned
+    where
+        Buffer<I>: std::borrow::ToOwned,
+        <Buffer<I> as std::borrow::ToOwned>::Owned: serde::Serialize,
+    {
+        std::borrow::ToOwned::to_owned(self.get())
+    }
+
     pub fn get_mut(&mut self) -> &mut Buffer<I> { self.split_mut().writer }
 
     pub fn split(&self) -> Split<'_, Buffer<I>> {
@@ -84,6 +117,32 @@ impl<I: StrongBuffer> Writer<I> {
         self.finish_buffer_swap(swap);
     }
 
+    /// Takes the writer by value, so if the future is dropped or leaked before
+    /// the swap finishes, the writer can't touch the buffers that are still being read
+    pub async fn swap_buffers_async(mut self) -> Self {
+        let swap = unsafe { self.start_buffer_swap() };
+        self.finish_buffer_swap_async(swap).await;
+        self
+    }
+
+    /// # Safety
+    ///
+    /// If the swap doesn't finish before the timeout, it is returned
+    /// and must be finished before the buffers are mutated again.
+    #[cfg(feature = "std")]
+    pub unsafe fn swap_buffers_timeout(&mut self, timeout: Duration) -> Result<(), Swap<Capture<I>>> {
+        let deadline = Instant::now().checked_add(timeout);
+        let swap = self.start_buffer_swap();
+
+        match deadline {
+            Some(deadline) => self.finish_buffer_swap_deadline(swap, deadline),
+            None => {
+                self.finish_buffer_swap(swap);
+                Ok(())
+            }
+        }
+    }
+
     pub fn try_swap_buffers_with<F: FnMut(&Self)>(&mut self, mut f: F) -> Result<(), CaptureError<I>> {
         let swap = unsafe { self.try_start_buffer_swap()? };
         let this = &*self;
@@ -100,6 +159,9 @@ impl<I: StrongBuffer> Writer<I> {
     }
 
     pub unsafe fn try_start_buffer_swap(&mut self) -> Result<Swap<Capture<I>>, CaptureError<I>> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("try_start_buffer_swap", generation = self.inner.generation()).entered();
+
         let inner = &*self.inner;
         let capture = inner.strategy.try_capture_readers(&mut self.tag)?;
 
@@ -107,8 +169,13 @@ impl<I: StrongBuffer> Writer<I> {
         // load `which` (`Ordering::Acquire`), so a `Release` ordering
         // is sufficient. The `Writer` cannot race with itself.
         inner.which.fetch_xor(true, Ordering::Release);
+        inner.bump_generation();
 
         let capture = inner.strategy.finish_capture_readers(&mut self.tag, capture);
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(active_readers = ?inner.strategy.active_readers(&capture), "started buffer swap");
+
         Ok(Swap(capture))
     }
 
@@ -125,6 +192,53 @@ impl<I: StrongBuffer> Writer<I> {
         self.finish_buffer_swap_with(swap, drop_in_place)
     }
 
+    pub fn poll_buffer_swap(&self, swap: &mut Swap<Capture<I>>, cx: &mut Context<'_>) -> Poll<()> {
+        let strategy = &self.inner.strategy;
+
+        if !strategy.readers_have_exited(&mut swap.0) {
+            strategy.register_waker(&mut swap.0, cx.waker());
+
+            if !strategy.readers_have_exited(&mut swap.0) {
+                return Poll::Pending
+            }
+        }
+
+        Poll::Ready(())
+    }
+
+    #[cfg(feature = "std")]
+    pub fn finish_buffer_swap_deadline(
+        &self,
+        mut swap: Swap<Capture<I>>,
+        deadline: Instant,
+    ) -> Result<(), Swap<Capture<I>>> {
+        let strategy = &self.inner.strategy;
+        #[cfg(feature = "tracing")]
+        let mut parked = false;
+
+        while !strategy.readers_have_exited(&mut swap.0) {
+            if Instant::now() >= deadline {
+                return Err(swap)
+            }
+
+            strategy.pause_until(&mut swap.0, deadline);
+
+            #[cfg(feature = "tracing")]
+            trace_parked(strategy, &swap.0, &mut parked);
+        }
+
+        strategy.finish_capture(&self.tag, swap.0);
+        Ok(())
+    }
+
+    /// Dropping the future before it completes drops the swap without finishing it
+    pub fn finish_buffer_swap_async(&self, swap: Swap<Capture<I>>) -> SwapFuture<'_, I> {
+        SwapFuture {
+            writer: self,
+            swap: Some(swap),
+        }
+    }
+
     #[allow(clippy::toplevel_ref_arg)]
     pub fn finish_buffer_swap_with<F: FnMut()>(&self, swap: Swap<Capture<I>>, ref mut f: F) {
         #[cold]
@@ -135,13 +249,22 @@ impl<I: StrongBuffer> Writer<I> {
         }
 
         fn finish_swap_with<S: Strategy>(strategy: &S, tag: &S::WriterTag, mut swap: S::Capture, f: &mut dyn FnMut()) {
+            #[cfg(feature = "tracing")]
+            let _span =
+                tracing::debug_span!("finish_buffer_swap", active_readers = ?strategy.active_readers(&swap)).entered();
+
             let on_drop = FinishSwapOnDrop {
                 strategy,
                 swap: &mut swap,
             };
+            #[cfg(feature = "tracing")]
+            let mut parked = false;
 
             while !strategy.readers_have_exited(on_drop.swap) {
                 cold(f, strategy, on_drop.swap);
+
+                #[cfg(feature = "tracing")]
+                trace_parked(strategy, on_drop.swap, &mut parked);
             }
 
             core::mem::forget(on_drop);
@@ -153,6 +276,27 @@ impl<I: StrongBuffer> Writer<I> {
     }
 }
 
+impl<I: StrongBuffer> Unpin for SwapFuture<'_, I> {}
+
+impl<I: StrongBuffer> Future for SwapFuture<'_, I> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let swap = this.swap.as_mut().expect("Tried to poll a completed `SwapFuture`");
+
+        if this.writer.poll_buffer_swap(swap, cx).is_pending() {
+            return Poll::Pending
+        }
+
+        if let Some(swap) = this.swap.take() {
+            this.writer.finish_buffer_swap(swap);
+        }
+
+        Poll::Ready(())
+    }
+}
+
 impl<I: StrongBuffer> AsRef<Buffer<I>> for Writer<I> {
     fn as_ref(&self) -> &Buffer<I> { self.get() }
 }
@@ -0,0 +1,33 @@
+use crate::{base::ReaderGuard, traits::StrongBuffer};
+
+#[cfg(feature = "std")]
+pub mod map;
+pub mod vec;
+
+/// Yields clones of the items, so that they can't outlive the guard
+pub struct Iter<'a, It, I: StrongBuffer, B: ?Sized> {
+    // borrows from the guard's buffer, which stays put when the guard moves
+    iter: It,
+    _guard: ReaderGuard<'a, I, B>,
+}
+
+impl<'a, It, I: StrongBuffer, B: ?Sized> Iter<'a, It, I, B> {
+    fn new(guard: ReaderGuard<'a, I, B>, iter: impl FnOnce(&'a B) -> It) -> Self {
+        let buffer: *const B = &*guard;
+
+        Self {
+            iter: iter(unsafe { &*buffer }),
+            _guard: guard,
+        }
+    }
+}
+
+impl<It: Iterator, I: StrongBuffer, B: ?Sized> Iterator for Iter<'_, It, I, B> {
+    type Item = It::Item;
+
+    fn next(&mut self) -> Option<Self::Item> { self.iter.next() }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
+}
+
+impl<It: ExactSizeIterator, I: StrongBuffer, B: ?Sized> ExactSizeIterator for Iter<'_, It, I, B> {}
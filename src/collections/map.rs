@@ -0,0 +1,161 @@
+use core::{
+    borrow::Borrow,
+    hash::{BuildHasher, Hash},
+};
+use std::{
+    collections::{hash_map, HashMap},
+    sync::Arc,
+};
+
+use crate::{
+    base::{Inner, Reader, ReaderGuard},
+    op::OpWriter,
+    strategy::saving::SavingStrategy,
+    traits::Operation,
+};
+
+type Maps<K, V, S> = [HashMap<K, V, S>; 2];
+type Strong<K, V, S> = Arc<Inner<Maps<K, V, S>, SavingStrategy>>;
+type Weak<K, V, S> = std::sync::Weak<Inner<Maps<K, V, S>, SavingStrategy>>;
+
+pub fn new<K, V>() -> (WriteHandle<K, V>, ReadHandle<K, V>)
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    with_hasher(Default::default())
+}
+
+pub fn with_hasher<K, V, S>(hasher: S) -> (WriteHandle<K, V, S>, ReadHandle<K, V, S>)
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    let front = HashMap::with_hasher(hasher.clone());
+    let back = HashMap::with_hasher(hasher);
+    let (writer, reader) = crate::strategy::saving::from_buffers(front, back);
+
+    (WriteHandle { writer: writer.into() }, ReadHandle { reader })
+}
+
+pub enum Op<K, V> {
+    Insert(K, V),
+    Remove(K),
+    Clear,
+}
+
+impl<K, V, S> Operation<HashMap<K, V, S>> for Op<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    S: BuildHasher,
+{
+    fn apply(&mut self, map: &mut HashMap<K, V, S>) {
+        match self {
+            Op::Insert(key, value) => {
+                map.insert(key.clone(), value.clone());
+            }
+            Op::Remove(key) => {
+                map.remove(key);
+            }
+            Op::Clear => map.clear(),
+        }
+    }
+
+    fn apply_final(self, map: &mut HashMap<K, V, S>) {
+        match self {
+            Op::Insert(key, value) => {
+                map.insert(key, value);
+            }
+            Op::Remove(key) => {
+                map.remove(&key);
+            }
+            Op::Clear => map.clear(),
+        }
+    }
+}
+
+pub struct WriteHandle<K, V, S = hash_map::RandomState> {
+    writer: OpWriter<Strong<K, V, S>, Op<K, V>>,
+}
+
+pub struct ReadHandle<K, V, S = hash_map::RandomState> {
+    reader: Reader<Weak<K, V, S>>,
+}
+
+type Entries<'a, K, V> = core::iter::Map<hash_map::Iter<'a, K, V>, fn((&'a K, &'a V)) -> (K, V)>;
+
+pub type Iter<'a, K, V, S> = super::Iter<'a, Entries<'a, K, V>, Strong<K, V, S>, HashMap<K, V, S>>;
+
+impl<K, V, S> WriteHandle<K, V, S>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    S: BuildHasher,
+{
+    pub fn reader(&self) -> ReadHandle<K, V, S> {
+        ReadHandle {
+            reader: self.writer.reader(),
+        }
+    }
+
+    pub fn insert(&mut self, key: K, value: V) { self.writer.push(Op::Insert(key, value)) }
+
+    pub fn remove(&mut self, key: K) { self.writer.push(Op::Remove(key)) }
+
+    pub fn clear(&mut self) { self.writer.push(Op::Clear) }
+
+    pub fn pending(&self) -> &[Op<K, V>] { self.writer.ops() }
+
+    pub fn publish(&mut self) { self.writer.swap_buffers() }
+}
+
+impl<K, V, S> Extend<Op<K, V>> for WriteHandle<K, V, S> {
+    fn extend<T: IntoIterator<Item = Op<K, V>>>(&mut self, iter: T) { self.writer.extend(iter) }
+}
+
+impl<K, V, S> ReadHandle<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    pub fn read(&self) -> ReaderGuard<'_, Strong<K, V, S>, HashMap<K, V, S>> { self.reader.get_shared() }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<ReaderGuard<'_, Strong<K, V, S>, V>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        ReaderGuard::try_map(self.read(), |map| map.get(key)).ok()
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.read().contains_key(key)
+    }
+
+    pub fn len(&self) -> usize { self.read().len() }
+
+    pub fn is_empty(&self) -> bool { self.read().is_empty() }
+
+    pub fn iter(&self) -> Iter<'_, K, V, S>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        let clone: fn((&K, &V)) -> (K, V) = |(key, value)| (key.clone(), value.clone());
+        super::Iter::new(self.read(), |map| map.iter().map(clone))
+    }
+}
+
+impl<K, V, S> Clone for ReadHandle<K, V, S> {
+    fn clone(&self) -> Self {
+        Self {
+            reader: self.reader.clone(),
+        }
+    }
+}
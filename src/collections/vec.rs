@@ -0,0 +1,151 @@
+use std::{sync::Arc, vec::Vec};
+
+use crate::{
+    base::{Inner, Reader, ReaderGuard},
+    op::OpWriter,
+    strategy::saving::SavingStrategy,
+    traits::Operation,
+};
+
+type Strong<T> = Arc<Inner<[Vec<T>; 2], SavingStrategy>>;
+type Weak<T> = std::sync::Weak<Inner<[Vec<T>; 2], SavingStrategy>>;
+
+pub fn new<T: Clone>() -> (VecWriter<T>, VecReader<T>) {
+    let (writer, reader) = crate::strategy::saving::new();
+
+    (
+        VecWriter {
+            writer: writer.into(),
+            len: 0,
+        },
+        VecReader { reader },
+    )
+}
+
+pub enum Op<T> {
+    Push(T),
+    Insert(usize, T),
+    Remove(usize),
+    SwapRemove(usize),
+    Truncate(usize),
+    Clear,
+}
+
+impl<T: Clone> Operation<Vec<T>> for Op<T> {
+    fn apply(&mut self, vec: &mut Vec<T>) {
+        match *self {
+            Op::Push(ref value) => vec.push(value.clone()),
+            Op::Insert(index, ref value) => vec.insert(index, value.clone()),
+            Op::Remove(index) => drop(vec.remove(index)),
+            Op::SwapRemove(index) => drop(vec.swap_remove(index)),
+            Op::Truncate(len) => vec.truncate(len),
+            Op::Clear => vec.clear(),
+        }
+    }
+
+    fn apply_final(self, vec: &mut Vec<T>) {
+        match self {
+            Op::Push(value) => vec.push(value),
+            Op::Insert(index, value) => vec.insert(index, value),
+            mut op => op.apply(vec),
+        }
+    }
+}
+
+pub struct VecWriter<T> {
+    writer: OpWriter<Strong<T>, Op<T>>,
+    // the length once all pending operations are applied, to check indices when they're pushed
+    len: usize,
+}
+
+pub struct VecReader<T> {
+    reader: Reader<Weak<T>>,
+}
+
+pub type Iter<'a, T> = super::Iter<'a, core::iter::Cloned<core::slice::Iter<'a, T>>, Strong<T>, [T]>;
+
+impl<T: Clone> VecWriter<T> {
+    pub fn reader(&self) -> VecReader<T> {
+        VecReader {
+            reader: self.writer.reader(),
+        }
+    }
+
+    pub fn len(&self) -> usize { self.len }
+
+    pub fn is_empty(&self) -> bool { self.len == 0 }
+
+    pub fn push(&mut self, value: T) { self.push_op(Op::Push(value)) }
+
+    pub fn insert(&mut self, index: usize, value: T) { self.push_op(Op::Insert(index, value)) }
+
+    pub fn remove(&mut self, index: usize) { self.push_op(Op::Remove(index)) }
+
+    pub fn swap_remove(&mut self, index: usize) { self.push_op(Op::SwapRemove(index)) }
+
+    pub fn truncate(&mut self, len: usize) { self.push_op(Op::Truncate(len)) }
+
+    pub fn clear(&mut self) { self.push_op(Op::Clear) }
+
+    pub fn pending(&self) -> &[Op<T>] { self.writer.ops() }
+
+    pub fn publish(&mut self) { self.writer.swap_buffers() }
+}
+
+impl<T> VecWriter<T> {
+    // an out of bounds op would only panic once it's applied during a swap, so check it now
+    fn push_op(&mut self, op: Op<T>) {
+        let len = self.len;
+
+        self.len = match op {
+            Op::Push(_) => len + 1,
+            Op::Insert(index, _) => {
+                assert!(index <= len, "insertion index (is {}) should be <= len (is {})", index, len);
+                len + 1
+            }
+            Op::Remove(index) => {
+                assert!(index < len, "removal index (is {}) should be < len (is {})", index, len);
+                len - 1
+            }
+            Op::SwapRemove(index) => {
+                assert!(index < len, "swap_remove index (is {}) should be < len (is {})", index, len);
+                len - 1
+            }
+            Op::Truncate(new_len) => len.min(new_len),
+            Op::Clear => 0,
+        };
+
+        self.writer.push(op)
+    }
+}
+
+impl<T> Extend<Op<T>> for VecWriter<T> {
+    fn extend<I: IntoIterator<Item = Op<T>>>(&mut self, iter: I) { iter.into_iter().for_each(|op| self.push_op(op)) }
+}
+
+impl<T> VecReader<T> {
+    pub fn read(&self) -> ReaderGuard<'_, Strong<T>, [T]> { ReaderGuard::map(self.reader.get_shared(), Vec::as_slice) }
+
+    pub fn get(&self, index: usize) -> Option<ReaderGuard<'_, Strong<T>, T>> {
+        ReaderGuard::try_map(self.reader.get_shared(), |vec| vec.get(index)).ok()
+    }
+
+    pub fn len(&self) -> usize { self.read().len() }
+
+    pub fn is_empty(&self) -> bool { self.read().is_empty() }
+
+    pub fn iter(&self) -> Iter<'_, T>
+    where
+        T: Clone,
+    {
+        super::Iter::new(self.read(), |slice| slice.iter().cloned())
+    }
+}
+
+impl<T> Clone for VecReader<T> {
+    fn clone(&self) -> Self {
+        Self {
+            reader: self.reader.clone(),
+        }
+    }
+}
@@ -13,11 +13,16 @@ pub struct DeferredWriter<I, T = WriterTag<I>, C = Capture<I>> {
 
 pub trait WaitingStrategy {}
 #[cfg(feature = "alloc")]
+impl WaitingStrategy for crate::strategy::epoch::EpochStrategy {}
+#[cfg(feature = "alloc")]
 impl WaitingStrategy for crate::strategy::saving::SavingStrategy {}
+impl WaitingStrategy for crate::strategy::seqlock::SeqLockStrategy {}
 #[cfg(feature = "alloc")]
 impl WaitingStrategy for crate::strategy::local_saving::LocalSavingStrategy {}
 #[cfg(feature = "std")]
 impl WaitingStrategy for crate::strategy::saving_park::SavingParkStrategy {}
+#[cfg(feature = "metrics")]
+impl<S: WaitingStrategy> WaitingStrategy for crate::strategy::stats::StrategyStats<S> {}
 
 impl<I: StrongBuffer> From<Writer<I>> for DeferredWriter<I>
 where
@@ -43,6 +48,29 @@ impl<I: StrongBuffer> DeferredWriter<I> {
         &mut self.writer
     }
 
+    #[cfg(feature = "std")]
+    pub fn finish_swap_deadline(&mut self, deadline: std::time::Instant) -> Option<&mut Writer<I>> {
+        if let Some(swap) = self.swap.take() {
+            if let Err(swap) = self.writer.finish_buffer_swap_deadline(swap, deadline) {
+                self.swap = Some(swap);
+                return None
+            }
+        }
+
+        Some(&mut self.writer)
+    }
+
+    pub async fn finish_swap_async(&mut self) -> &mut Writer<I> {
+        // the swap is only taken once it's complete, so leaking
+        // this future can't leave the buffers in a bad state
+        if let Some(swap) = &mut self.swap {
+            let writer = &self.writer;
+            core::future::poll_fn(|cx| writer.poll_buffer_swap(swap, cx)).await;
+        }
+
+        self.finish_swap()
+    }
+
     pub fn start_swap(&mut self) { self.swap = Some(unsafe { self.writer.start_buffer_swap() }); }
 
     pub fn into_inner(mut self) -> Writer<I> {
@@ -1,6 +1,6 @@
 use crate::{
     base::Inner,
-    traits::{RawDoubleBuffer, RawParts, Strategy, StrongBuffer, WeakBuffer},
+    traits::{RawDoubleBuffer, RawParts, Strategy, StrongBuffer, WeakBuffer, WeakBufferPtr},
 };
 
 unsafe impl<'a, S: Strategy, R: RawDoubleBuffer + ?Sized> RawParts for &'a mut Inner<R, S> {
@@ -31,3 +31,7 @@ unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> WeakBuffer for &Inner<R, S
 
     fn upgrade(&self) -> Result<Self::Strong, Self::UpgradeError> { Ok(*self) }
 }
+
+unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> WeakBufferPtr for &Inner<R, S> {
+    fn as_ptr(&self) -> *const Inner<R, S> { *self }
+}
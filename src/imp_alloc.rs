@@ -1,6 +1,6 @@
 use crate::{
     athin, thin,
-    traits::{RawParts, StrongBuffer, WeakBuffer},
+    traits::{RawParts, StrongBuffer, WeakBuffer, WeakBufferPtr},
 };
 use std::{boxed::Box, rc, sync};
 
@@ -12,7 +12,7 @@ use crate::{
 #[derive(Debug)]
 pub struct UpgradeError;
 
-unsafe impl<'a, S: Strategy, R: RawDoubleBuffer + ?Sized> RawParts for sync::Arc<Inner<R, S>> {
+unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> RawParts for sync::Arc<Inner<R, S>> {
     type Strategy = S;
     type Raw = R;
 
@@ -45,7 +45,11 @@ unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> WeakBuffer for sync::Weak<
     fn upgrade(&self) -> Result<Self::Strong, Self::UpgradeError> { self.upgrade().ok_or(UpgradeError) }
 }
 
-unsafe impl<'a, S: Strategy, R: RawDoubleBuffer + ?Sized> RawParts for rc::Rc<Inner<R, S>> {
+unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> WeakBufferPtr for sync::Weak<Inner<R, S>> {
+    fn as_ptr(&self) -> *const Inner<R, S> { sync::Weak::as_ptr(self) }
+}
+
+unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> RawParts for rc::Rc<Inner<R, S>> {
     type Strategy = S;
     type Raw = R;
 
@@ -78,36 +82,45 @@ unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> WeakBuffer for rc::Weak<In
     fn upgrade(&self) -> Result<Self::Strong, Self::UpgradeError> { self.upgrade().ok_or(UpgradeError) }
 }
 
+unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> WeakBufferPtr for rc::Weak<Inner<R, S>> {
+    fn as_ptr(&self) -> *const Inner<R, S> { rc::Weak::as_ptr(self) }
+}
+
 unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> RawParts for Box<athin::AthinInner<Inner<R, S>>> {
     type Strategy = S;
     type Raw = R;
 
     type Strong = athin::Athin<Inner<R, S>>;
-    type Weak = athin::Athin<Inner<R, S>>;
+    type Weak = athin::AthinWeak<Inner<R, S>>;
 
     fn raw_parts(self) -> (Self::Strong, Self::Weak) {
         let thin = athin::Athin::from(self);
-        (thin.clone(), thin)
+        let weak = athin::Athin::downgrade(&thin);
+        (thin, weak)
     }
 }
 
 unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> StrongBuffer for athin::Athin<Inner<R, S>> {
     type Strategy = S;
     type Raw = R;
-    type Weak = Self;
+    type Weak = athin::AthinWeak<Inner<R, S>>;
 
-    fn downgrade(&self) -> Self::Weak { self.clone() }
+    fn downgrade(&self) -> Self::Weak { athin::Athin::downgrade(self) }
 }
 
-unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> WeakBuffer for athin::Athin<Inner<R, S>> {
+unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> WeakBuffer for athin::AthinWeak<Inner<R, S>> {
     type Strategy = S;
     type Raw = R;
-    type Strong = Self;
-    type UpgradeError = core::convert::Infallible;
+    type Strong = athin::Athin<Inner<R, S>>;
+    type UpgradeError = UpgradeError;
 
-    fn is_dangling(&self) -> bool { false }
+    fn is_dangling(&self) -> bool { athin::AthinWeak::is_dangling(self) }
 
-    fn upgrade(&self) -> Result<Self::Strong, Self::UpgradeError> { Ok(self.clone()) }
+    fn upgrade(&self) -> Result<Self::Strong, Self::UpgradeError> { athin::AthinWeak::upgrade(self).ok_or(UpgradeError) }
+}
+
+unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> WeakBufferPtr for athin::AthinWeak<Inner<R, S>> {
+    fn as_ptr(&self) -> *const Inner<R, S> { athin::AthinWeak::as_ptr(self) }
 }
 
 unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> RawParts for Box<thin::ThinInner<Inner<R, S>>> {
@@ -115,29 +128,34 @@ unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> RawParts for Box<thin::Thi
     type Raw = R;
 
     type Strong = thin::Thin<Inner<R, S>>;
-    type Weak = thin::Thin<Inner<R, S>>;
+    type Weak = thin::ThinWeak<Inner<R, S>>;
 
     fn raw_parts(self) -> (Self::Strong, Self::Weak) {
         let thin = thin::Thin::from(self);
-        (thin.clone(), thin)
+        let weak = thin::Thin::downgrade(&thin);
+        (thin, weak)
     }
 }
 
 unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> StrongBuffer for thin::Thin<Inner<R, S>> {
     type Strategy = S;
     type Raw = R;
-    type Weak = Self;
+    type Weak = thin::ThinWeak<Inner<R, S>>;
 
-    fn downgrade(&self) -> Self::Weak { self.clone() }
+    fn downgrade(&self) -> Self::Weak { thin::Thin::downgrade(self) }
 }
 
-unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> WeakBuffer for thin::Thin<Inner<R, S>> {
+unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> WeakBuffer for thin::ThinWeak<Inner<R, S>> {
     type Strategy = S;
     type Raw = R;
-    type Strong = Self;
-    type UpgradeError = core::convert::Infallible;
+    type Strong = thin::Thin<Inner<R, S>>;
+    type UpgradeError = UpgradeError;
+
+    fn is_dangling(&self) -> bool { thin::ThinWeak::is_dangling(self) }
 
-    fn is_dangling(&self) -> bool { false }
+    fn upgrade(&self) -> Result<Self::Strong, Self::UpgradeError> { thin::ThinWeak::upgrade(self).ok_or(UpgradeError) }
+}
 
-    fn upgrade(&self) -> Result<Self::Strong, Self::UpgradeError> { Ok(self.clone()) }
+unsafe impl<S: Strategy, R: RawDoubleBuffer + ?Sized> WeakBufferPtr for thin::ThinWeak<Inner<R, S>> {
+    fn as_ptr(&self) -> *const Inner<R, S> { thin::ThinWeak::as_ptr(self) }
 }
@@ -3,13 +3,25 @@
 
 #[cfg(all(feature = "alloc", not(feature = "std")))]
 extern crate alloc as std;
+#[cfg(feature = "tracing")]
+extern crate tracing_crate as tracing;
 
 pub mod base;
+#[cfg(feature = "alloc")]
+pub mod collections;
 pub mod deferred;
 #[cfg(feature = "alloc")]
 pub mod op;
+#[cfg(feature = "std")]
+pub mod publisher;
+pub mod raw;
+#[cfg(feature = "std")]
+pub mod shared;
 pub mod strategy;
 pub mod traits;
+pub mod triple;
+#[cfg(feature = "wal")]
+pub mod wal;
 
 #[cfg(feature = "alloc")]
 pub mod athin;
@@ -21,7 +33,8 @@ pub mod thin;
 mod imp;
 #[cfg(feature = "alloc")]
 mod imp_alloc;
-mod raw;
+#[cfg(feature = "alloc")]
+mod waker;
 
 #[cfg(feature = "alloc")]
 pub use imp_alloc::UpgradeError;
@@ -3,14 +3,16 @@
 use crate::{
     base::Writer,
     deferred::{DeferredWriter, WaitingStrategy},
-    traits::{Buffer, Capture, Operation, StrongBuffer, WriterTag},
+    traits::{Buffer, Capture, Operation, OperationWith, StrongBuffer, WriterTag},
 };
 
 use crate::op_list::OpList;
+use std::boxed::Box;
 
-pub struct OpWriter<I, O, T = WriterTag<I>, C = Capture<I>> {
+pub struct OpWriter<I, O, T = WriterTag<I>, C = Capture<I>, X = ()> {
     writer: DeferredWriter<I, T, C>,
     ops: OpList<O>,
+    extra: X,
 }
 
 impl<I: StrongBuffer, O> From<Writer<I>> for OpWriter<I, O>
@@ -28,14 +30,31 @@ pub struct Operations<'a, O> {
     list: &'a mut OpList<O>,
 }
 
+type ApplyFinal<B> = Box<dyn FnOnce(&mut B) + Send>;
+
+pub struct FnOp<B: ?Sized> {
+    apply: Box<dyn Fn(&mut B) + Send>,
+    apply_final: Option<ApplyFinal<B>>,
+}
+
 impl<I, O, T, C> OpWriter<I, O, T, C> {
-    pub const fn new(writer: DeferredWriter<I, T, C>) -> Self {
+    pub const fn new(writer: DeferredWriter<I, T, C>) -> Self { Self::with_extra(writer, ()) }
+}
+
+impl<I, O, T, C, X> OpWriter<I, O, T, C, X> {
+    /// `extra` is only visible to the writer, and is passed to each operation alongside the buffer
+    pub const fn with_extra(writer: DeferredWriter<I, T, C>, extra: X) -> Self {
         Self {
             writer,
             ops: OpList::new(),
+            extra,
         }
     }
 
+    pub fn extra(&self) -> &X { &self.extra }
+
+    pub fn extra_mut(&mut self) -> &mut X { &mut self.extra }
+
     pub fn as_mut_parts(&mut self) -> (&Writer<I, T>, Operations<'_, O>) {
         (&self.writer, Operations { list: &mut self.ops })
     }
@@ -47,14 +66,30 @@ impl<I, O, T, C> OpWriter<I, O, T, C> {
     pub fn push(&mut self, op: O) { self.ops.push(op) }
 
     pub fn ops(&self) -> &[O] { &self.ops }
+
+    pub fn start_pending_log(&mut self)
+    where
+        O: Clone,
+    {
+        self.ops.start_log()
+    }
+
+    pub fn drain_pending_log(&mut self) -> OpList<O>
+    where
+        O: Clone,
+    {
+        self.ops.drain_log()
+    }
 }
 
-impl<I: StrongBuffer, O: Operation<Buffer<I>>> OpWriter<I, O> {
+impl<I: StrongBuffer, O: OperationWith<Buffer<I>, X>, X> OpWriter<I, O, WriterTag<I>, Capture<I>, X> {
     pub fn swap_buffers(&mut self) { self.swap_buffers_with(|_, _| ()) }
 
-    pub fn swap_buffers_with<F: FnMut(&Writer<I>, Operations<'_, O>)>(&mut self, f: F) {
-        let (writer, ops) = self.finish_swap_with(f);
-        ops.apply(writer.get_mut());
+    pub fn swap_buffers_with<F: FnMut(&Writer<I>, Operations<'_, O>)>(&mut self, mut f: F) {
+        let mut ops = Operations { list: &mut self.ops };
+        let f = move |writer: &_| f(writer, ops.by_ref());
+        let writer = self.writer.finish_swap_with(f);
+        self.ops.apply_with(writer.get_mut(), &mut self.extra);
         self.start_swap()
     }
 
@@ -71,14 +106,25 @@ impl<I: StrongBuffer, O: Operation<Buffer<I>>> OpWriter<I, O> {
 
     pub fn start_swap(&mut self) { self.writer.start_swap() }
 
+    pub fn apply_log(&mut self, log: OpList<O>) {
+        self.ops.extend(log.into_pending());
+        self.swap_buffers()
+    }
+
     pub fn into_raw_parts(self) -> (DeferredWriter<I>, OpList<O>) { (self.writer, self.ops) }
+
+    pub fn into_raw_parts_with_extra(self) -> (DeferredWriter<I>, OpList<O>, X) { (self.writer, self.ops, self.extra) }
 }
 
-impl<I, O, T, C> Extend<O> for OpWriter<I, O, T, C> {
+impl<I, B: ?Sized, T, C, X> OpWriter<I, FnOp<B>, T, C, X> {
+    pub fn push_fn<F: Fn(&mut B) + Send + 'static>(&mut self, f: F) { self.push(FnOp::new(f)) }
+}
+
+impl<I, O, T, C, X> Extend<O> for OpWriter<I, O, T, C, X> {
     fn extend<Iter: IntoIterator<Item = O>>(&mut self, iter: Iter) { self.ops.extend(iter) }
 }
 
-impl<I, O, T, C> core::ops::Deref for OpWriter<I, O, T, C> {
+impl<I, O, T, C, X> core::ops::Deref for OpWriter<I, O, T, C, X> {
     type Target = Writer<I, T>;
 
     fn deref(&self) -> &Self::Target { &self.writer }
@@ -91,7 +137,7 @@ impl<O> Operations<'_, O> {
 
     pub fn reserve(&mut self, additional: usize) { self.list.reserve(additional) }
 
-    pub fn by_ref(&mut self) -> Operations<'_, O> { Operations { list: &mut self.list } }
+    pub fn by_ref(&mut self) -> Operations<'_, O> { Operations { list: self.list } }
 }
 
 impl<O> Extend<O> for Operations<'_, O> {
@@ -103,3 +149,34 @@ impl<O> core::ops::Deref for Operations<'_, O> {
 
     fn deref(&self) -> &Self::Target { self.list }
 }
+
+impl<B: ?Sized> FnOp<B> {
+    pub fn new<F: Fn(&mut B) + Send + 'static>(f: F) -> Self {
+        Self {
+            apply: Box::new(f),
+            apply_final: None,
+        }
+    }
+
+    pub fn with_final<F, G>(f: F, g: G) -> Self
+    where
+        F: Fn(&mut B) + Send + 'static,
+        G: FnOnce(&mut B) + Send + 'static,
+    {
+        Self {
+            apply: Box::new(f),
+            apply_final: Some(Box::new(g)),
+        }
+    }
+}
+
+impl<B: ?Sized> Operation<B> for FnOp<B> {
+    fn apply(&mut self, buffer: &mut B) { (self.apply)(buffer) }
+
+    fn apply_final(self, buffer: &mut B) {
+        match self.apply_final {
+            Some(apply_final) => apply_final(buffer),
+            None => (self.apply)(buffer),
+        }
+    }
+}
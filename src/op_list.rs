@@ -1,13 +1,17 @@
 use core::ops::Deref;
 use std::vec::Vec;
 
-use crate::traits::Operation;
+use crate::traits::{Operation, OperationWith};
 
 const POISON_BIT: usize = (!0) ^ (!0 >> 1);
 
 pub struct OpList<O> {
     operations: Vec<O>,
     applied: usize,
+    logged: usize,
+    // operations that were removed before they were logged, only kept once logging starts
+    log: Vec<O>,
+    clone_for_log: Option<fn(&O) -> O>,
 }
 
 impl<O> OpList<O> {
@@ -15,14 +19,48 @@ impl<O> OpList<O> {
         Self {
             operations: Vec::new(),
             applied: 0,
+            logged: 0,
+            log: Vec::new(),
+            clone_for_log: None,
         }
     }
 
     pub fn applied(&self) -> usize { self.applied }
 
+    pub fn pending(&self) -> &[O] { &self.operations[self.applied & !POISON_BIT..] }
+
+    /// Keep every operation that hasn't been logged yet until the next `drain_log`,
+    /// this happens automatically on the first `drain_log`
+    pub fn start_log(&mut self)
+    where
+        O: Clone,
+    {
+        self.clone_for_log = Some(O::clone);
+    }
+
+    pub fn drain_log(&mut self) -> OpList<O>
+    where
+        O: Clone,
+    {
+        self.start_log();
+        let mut log = core::mem::take(&mut self.log);
+        log.extend_from_slice(&self.operations[self.logged..]);
+        self.logged = self.operations.len();
+        log.into()
+    }
+
+    pub fn into_pending(mut self) -> Vec<O> { self.operations.split_off(self.applied & !POISON_BIT) }
+
     pub fn apply<B: ?Sized>(&mut self, buffer: &mut B)
     where
         O: Operation<B>,
+    {
+        self.apply_with(buffer, &mut ())
+    }
+
+    pub fn apply_with<B: ?Sized, X: ?Sized>(&mut self, buffer: &mut B, extra: &mut X)
+    where
+        O: OperationWith<B, X>,
     {
         struct SetOnDrop<'a>(&'a mut usize, usize);
 
@@ -31,7 +69,7 @@ impl<O> OpList<O> {
         }
 
         if self.applied & POISON_BIT == 0 {
-            self.apply_final(buffer);
+            self.apply_final(buffer, extra);
         }
 
         self.applied |= POISON_BIT;
@@ -40,7 +78,7 @@ impl<O> OpList<O> {
 
         for operation in self.operations.iter_mut() {
             *applied += 1;
-            operation.apply(buffer);
+            operation.apply(buffer, extra);
         }
 
         drop(set_on_drop);
@@ -50,9 +88,9 @@ impl<O> OpList<O> {
     pub fn assume_no_panic(&mut self) { self.applied &= !POISON_BIT; }
 
     #[inline(always)]
-    fn apply_final<B: ?Sized>(&mut self, buffer: &mut B)
+    fn apply_final<B: ?Sized, X: ?Sized>(&mut self, buffer: &mut B, extra: &mut X)
     where
-        O: Operation<B>,
+        O: OperationWith<B, X>,
     {
         struct Fixer<'a, T> {
             begin: *mut T,
@@ -70,6 +108,10 @@ impl<O> OpList<O> {
             }
         }
 
+        // the operations that are about to be removed are moved over to `log` if they haven't been logged yet
+        let mut logged = self.logged;
+        self.logged = self.logged.saturating_sub(self.applied);
+
         let len = self.operations.len();
         let ptr = self.operations.as_mut_ptr();
 
@@ -82,11 +124,16 @@ impl<O> OpList<O> {
 
         while self.applied != 0 {
             unsafe {
+                match self.clone_for_log {
+                    Some(clone) if logged == 0 => self.log.push(clone(&*fixer.curr)),
+                    _ => logged = logged.saturating_sub(1),
+                }
+
                 self.applied -= 1;
                 fixer.len -= 1;
                 let ptr = fixer.curr;
                 fixer.curr = fixer.curr.add(1);
-                ptr.read().apply_final(buffer)
+                ptr.read().apply_final(buffer, extra)
             }
         }
     }
@@ -96,6 +143,22 @@ impl<O> OpList<O> {
     pub fn reserve(&mut self, additional: usize) { self.operations.reserve(additional) }
 }
 
+impl<O> Default for OpList<O> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<O> From<Vec<O>> for OpList<O> {
+    fn from(operations: Vec<O>) -> Self {
+        Self {
+            operations,
+            applied: 0,
+            logged: 0,
+            log: Vec::new(),
+            clone_for_log: None,
+        }
+    }
+}
+
 impl<O> Extend<O> for OpList<O> {
     fn extend<T: IntoIterator<Item = O>>(&mut self, iter: T) { self.operations.extend(iter) }
 }
@@ -105,3 +168,19 @@ impl<O> Deref for OpList<O> {
 
     fn deref(&self) -> &Self::Target { &self.operations }
 }
+
+// only the pending operations are serialized, so a deserialized list
+// hasn't been applied to any buffer yet
+#[cfg(feature = "serde")]
+impl<O: serde::Serialize> serde::Serialize for OpList<O> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.pending())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, O: serde::Deserialize<'de>> serde::Deserialize<'de> for OpList<O> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Self::from)
+    }
+}
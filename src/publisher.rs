@@ -0,0 +1,203 @@
+#![forbid(unsafe_code)]
+
+use crate::{
+    op::OpWriter,
+    traits::{Buffer, Operation, StrongBuffer},
+};
+
+use std::{
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// When the publisher thread swaps the buffers, besides explicit flushes
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PublishPolicy {
+    interval: Option<Duration>,
+    batch_size: Option<usize>,
+}
+
+/// The publisher thread has stopped, the value that couldn't be sent is returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stopped<T = ()>(pub T);
+
+enum Message<O> {
+    Op(O),
+    Flush(Sender<()>),
+    Shutdown,
+}
+
+/// Owns an `OpWriter` on a dedicated thread, which applies pushed operations
+/// and swaps the buffers according to a `PublishPolicy`
+pub struct Publisher<I: StrongBuffer, O> {
+    handle: PublishHandle<O>,
+    thread: Option<JoinHandle<OpWriter<I, O>>>,
+}
+
+/// A cloneable handle to a `Publisher`'s thread
+pub struct PublishHandle<O> {
+    sender: Sender<Message<O>>,
+}
+
+impl<O> Clone for PublishHandle<O> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl PublishPolicy {
+    /// Only publish on explicit flushes
+    pub const fn new() -> Self {
+        Self {
+            interval: None,
+            batch_size: None,
+        }
+    }
+
+    /// Publish pending operations once `interval` has passed since the last publish
+    pub const fn interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Publish once `batch_size` operations are pending
+    pub const fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+}
+
+impl<I, O> Publisher<I, O>
+where
+    I: StrongBuffer + Send + 'static,
+    O: Operation<Buffer<I>> + Send + 'static,
+    OpWriter<I, O>: Send,
+{
+    pub fn spawn(writer: OpWriter<I, O>, policy: PublishPolicy) -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        let thread = thread::Builder::new()
+            .name("double-buffer-publisher".into())
+            .spawn(move || run(writer, receiver, policy))
+            .expect("Could not spawn the publisher thread");
+
+        Self {
+            handle: PublishHandle { sender },
+            thread: Some(thread),
+        }
+    }
+}
+
+impl<I: StrongBuffer, O> Publisher<I, O> {
+    pub fn handle(&self) -> PublishHandle<O> { self.handle.clone() }
+
+    pub fn push(&self, op: O) -> Result<(), Stopped<O>> { self.handle.push(op) }
+
+    pub fn flush(&self) -> Result<(), Stopped> { self.handle.flush() }
+
+    /// Publish any pending operations, stop the thread, and give back the writer
+    pub fn shutdown(mut self) -> OpWriter<I, O> { self.stop().expect("the publisher thread was already stopped") }
+
+    fn stop(&mut self) -> Option<OpWriter<I, O>> {
+        let thread = self.thread.take()?;
+        let _ = self.handle.sender.send(Message::Shutdown);
+
+        match thread.join() {
+            Ok(writer) => Some(writer),
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+}
+
+impl<I: StrongBuffer, O> Drop for Publisher<I, O> {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            self.stop();
+        }
+    }
+}
+
+impl<O> PublishHandle<O> {
+    pub fn push(&self, op: O) -> Result<(), Stopped<O>> {
+        self.sender.send(Message::Op(op)).map_err(|mpsc::SendError(message)| match message {
+            Message::Op(op) => Stopped(op),
+            _ => unreachable!(),
+        })
+    }
+
+    /// Publish all operations pushed before this call, and block until the buffers are swapped
+    pub fn flush(&self) -> Result<(), Stopped> {
+        let (done, wait) = mpsc::channel();
+        self.sender.send(Message::Flush(done)).map_err(|_| Stopped(()))?;
+        wait.recv().map_err(|_| Stopped(()))
+    }
+}
+
+fn run<I: StrongBuffer, O: Operation<Buffer<I>>>(
+    mut writer: OpWriter<I, O>,
+    receiver: Receiver<Message<O>>,
+    policy: PublishPolicy,
+) -> OpWriter<I, O> {
+    let next_deadline = || policy.interval.map(|interval| Instant::now() + interval);
+
+    let mut pending = 0;
+    let mut deadline = next_deadline();
+
+    loop {
+        let message = match deadline {
+            None => receiver.recv().ok(),
+            Some(at) => match receiver.recv_timeout(at.saturating_duration_since(Instant::now())) {
+                Ok(message) => Some(message),
+                Err(RecvTimeoutError::Disconnected) => None,
+                Err(RecvTimeoutError::Timeout) => {
+                    if pending != 0 {
+                        publish(&mut writer, pending);
+                        pending = 0;
+                    }
+
+                    deadline = next_deadline();
+                    continue
+                }
+            },
+        };
+
+        let flushed = match message {
+            Some(Message::Op(op)) => {
+                writer.push(op);
+                pending += 1;
+
+                if policy.batch_size.is_none_or(|batch_size| pending < batch_size) {
+                    continue
+                }
+
+                None
+            }
+            Some(Message::Flush(done)) => Some(done),
+            Some(Message::Shutdown) | None => {
+                if pending != 0 {
+                    publish(&mut writer, pending);
+                }
+
+                return writer
+            }
+        };
+
+        publish(&mut writer, pending);
+        pending = 0;
+        deadline = next_deadline();
+
+        if let Some(done) = flushed {
+            let _ = done.send(());
+        }
+    }
+}
+
+fn publish<I: StrongBuffer, O: Operation<Buffer<I>>>(writer: &mut OpWriter<I, O>, _pending: usize) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(ops = _pending, "publishing operations");
+
+    writer.swap_buffers();
+}
@@ -1,4 +1,117 @@
-use crate::traits::RawDoubleBuffer;
+use crate::traits::{RawDoubleBuffer, RawTripleBuffer};
+
+#[cfg(feature = "alloc")]
+use crate::traits::Operation;
+use core::ptr::{addr_of, addr_of_mut};
+#[cfg(feature = "alloc")]
+use std::boxed::Box;
+#[cfg(feature = "alloc")]
+use core::ops::{Deref, DerefMut};
+#[cfg(feature = "alloc")]
+use std::vec::Vec;
+
+/// Two buffers behind separate pointers, such as `Box<B>` or `&mut B`
+pub struct Pair<P> {
+    halves: [P; 2],
+}
+
+/// Two inline buffers, each padded to a cache line to avoid false sharing
+pub struct Padded<B> {
+    halves: [CacheAligned<B>; 2],
+}
+
+// used instead of `CachePadded`, which only exposes the value through `Deref`, so `split` would
+// have to borrow a half that the writer may be mutating, which is unsound with optimistic seqlock
+// reads. `repr(C)` puts the value at the start, so `split` can find it with a cast instead
+#[repr(C, align(128))]
+struct CacheAligned<B>(B);
+
+/// Two runtime-sized halves of equal length
+///
+/// The halves can only be resized through `Resize`, so that an `OpWriter`
+/// replays the resize on the other half
+#[cfg(feature = "alloc")]
+pub struct SliceBuffers<B> {
+    halves: [Half<B>; 2],
+}
+
+/// One half of a `SliceBuffers`, derefs to a slice
+#[cfg(feature = "alloc")]
+pub struct Half<B>(Vec<B>);
+
+/// Resize a `Half`, filling any new elements with clones of `value`
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Resize<B> {
+    pub len: usize,
+    pub value: B,
+}
+
+#[cfg(feature = "alloc")]
+impl<B> SliceBuffers<B> {
+    pub fn new(front: Vec<B>, back: Vec<B>) -> Self {
+        assert_eq!(
+            front.len(),
+            back.len(),
+            "Both halves of a `SliceBuffers` must have the same length"
+        );
+
+        Self {
+            halves: [Half(front), Half(back)],
+        }
+    }
+
+    pub fn from_snapshot(snapshot: Vec<B>) -> Self
+    where
+        B: Clone,
+    {
+        Self::new(snapshot.clone(), snapshot)
+    }
+
+    pub fn len(&self) -> usize { self.halves[0].len() }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    pub fn into_halves(self) -> (Vec<B>, Vec<B>) {
+        let [Half(front), Half(back)] = self.halves;
+        (front, back)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<B> Half<B> {
+    pub fn as_slice(&self) -> &[B] { &self.0 }
+
+    pub fn as_mut_slice(&mut self) -> &mut [B] { &mut self.0 }
+}
+
+#[cfg(feature = "alloc")]
+impl<B> Deref for Half<B> {
+    type Target = [B];
+
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+#[cfg(feature = "alloc")]
+impl<B> DerefMut for Half<B> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 }
+}
+
+#[cfg(feature = "alloc")]
+impl<B: Clone> Operation<Half<B>> for Resize<B> {
+    fn apply(&mut self, half: &mut Half<B>) { half.0.resize(self.len, self.value.clone()) }
+
+    fn apply_final(self, half: &mut Half<B>) { half.0.resize(self.len, self.value) }
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl<B> RawDoubleBuffer for SliceBuffers<B> {
+    type Buffer = Half<B>;
+
+    unsafe fn split(this: *mut Self, which: bool) -> (*mut Self::Buffer, *const Self::Buffer) {
+        RawDoubleBuffer::split(core::ptr::addr_of_mut!((*this).halves), which)
+    }
+}
 
 unsafe impl<B> RawDoubleBuffer for [B; 2] {
     type Buffer = B;
@@ -15,7 +128,7 @@ unsafe impl<B> RawDoubleBuffer for [B] {
     type Buffer = [B];
 
     unsafe fn split(this: *mut Self, which: bool) -> (*mut Self::Buffer, *const Self::Buffer) {
-        let len = (*this).len() / 2;
+        let len = (&*this).len() / 2;
         let ptr = this as *mut B;
 
         let writer = usize::from(which);
@@ -30,3 +143,66 @@ unsafe impl<B> RawDoubleBuffer for [B] {
         )
     }
 }
+
+impl<P> Pair<P> {
+    pub const fn new(front: P, back: P) -> Self { Self { halves: [front, back] } }
+
+    pub fn into_halves(self) -> (P, P) {
+        let [front, back] = self.halves;
+        (front, back)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<B> Pair<Box<B>> {
+    pub fn boxed(front: B, back: B) -> Self { Self::new(Box::new(front), Box::new(back)) }
+}
+
+// the pointers are only dereferenced as places, so no references to either half are created
+
+#[cfg(feature = "alloc")]
+unsafe impl<B: ?Sized> RawDoubleBuffer for Pair<Box<B>> {
+    type Buffer = B;
+
+    unsafe fn split(this: *mut Self, which: bool) -> (*mut Self::Buffer, *const Self::Buffer) {
+        let (writer, reader) = RawDoubleBuffer::split(addr_of_mut!((*this).halves), which);
+        (addr_of_mut!(**writer), addr_of!(**reader))
+    }
+}
+
+unsafe impl<B: ?Sized> RawDoubleBuffer for Pair<&mut B> {
+    type Buffer = B;
+
+    unsafe fn split(this: *mut Self, which: bool) -> (*mut Self::Buffer, *const Self::Buffer) {
+        let (writer, reader) = RawDoubleBuffer::split(addr_of_mut!((*this).halves), which);
+        (addr_of_mut!(**writer), addr_of!(**reader))
+    }
+}
+
+impl<B> Padded<B> {
+    pub const fn new(front: B, back: B) -> Self {
+        Self {
+            halves: [CacheAligned(front), CacheAligned(back)],
+        }
+    }
+
+    pub fn into_halves(self) -> (B, B) {
+        let [front, back] = self.halves;
+        (front.0, back.0)
+    }
+}
+
+unsafe impl<B> RawDoubleBuffer for Padded<B> {
+    type Buffer = B;
+
+    unsafe fn split(this: *mut Self, which: bool) -> (*mut Self::Buffer, *const Self::Buffer) {
+        let (writer, reader) = RawDoubleBuffer::split(addr_of_mut!((*this).halves), which);
+        (writer.cast::<B>(), reader.cast::<B>())
+    }
+}
+
+unsafe impl<B> RawTripleBuffer for [B; 3] {
+    type Buffer = B;
+
+    unsafe fn get(this: *mut Self, index: usize) -> *mut Self::Buffer { (this as *mut B).add(index) }
+}
@@ -0,0 +1,125 @@
+#![forbid(unsafe_code)]
+
+use crate::{
+    base::Reader,
+    op::OpWriter,
+    traits::{Buffer, Operation, StrongBuffer},
+};
+
+use parking_lot::Mutex;
+use std::{sync::Arc, vec::Vec};
+
+/// A cloneable handle to an `OpWriter`, for multiple producers
+///
+/// Producers enqueue operations, and whichever caller wins the lock
+/// applies every queued operation and swaps the buffers for everyone.
+pub struct SharedWriter<I: StrongBuffer, O> {
+    shared: Arc<Shared<I, O>>,
+}
+
+struct Shared<I: StrongBuffer, O> {
+    queue: Mutex<Vec<O>>,
+    writer: Mutex<OpWriter<I, O>>,
+}
+
+impl<I: StrongBuffer, O> Clone for SharedWriter<I, O> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<I: StrongBuffer, O> From<OpWriter<I, O>> for SharedWriter<I, O> {
+    fn from(writer: OpWriter<I, O>) -> Self { Self::new(writer) }
+}
+
+impl<I: StrongBuffer, O> SharedWriter<I, O> {
+    pub fn new(writer: OpWriter<I, O>) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                queue: Mutex::new(Vec::new()),
+                writer: Mutex::new(writer),
+            }),
+        }
+    }
+
+    pub fn reader(&self) -> Reader<I::Weak> { self.shared.writer.lock().reader() }
+
+    /// The number of operations that haven't been handed to the writer yet
+    pub fn queued(&self) -> usize { self.shared.queue.lock().len() }
+
+    /// Enqueue an operation, it will be applied by the next flush
+    pub fn push(&self, op: O) { self.shared.queue.lock().push(op) }
+
+    /// Returns the writer if this is the last handle
+    pub fn try_into_inner(self) -> Result<OpWriter<I, O>, Self> {
+        match Arc::try_unwrap(self.shared) {
+            Ok(shared) => {
+                let mut writer = shared.writer.into_inner();
+                writer.extend(shared.queue.into_inner());
+                Ok(writer)
+            }
+            Err(shared) => Err(Self { shared }),
+        }
+    }
+}
+
+impl<I: StrongBuffer, O: Operation<Buffer<I>>> SharedWriter<I, O> {
+    /// Enqueue an operation, and flush if no other thread is flushing
+    pub fn submit(&self, op: O) -> bool {
+        self.push(op);
+        self.try_flush()
+    }
+
+    /// Apply all queued operations and swap the buffers, unless another thread is
+    /// already flushing, in which case that thread will pick up the queued operations
+    ///
+    /// Returns true if this thread flushed
+    pub fn try_flush(&self) -> bool {
+        let mut flushed = false;
+
+        // an operation pushed while the lock was held may have missed the
+        // flush, so check again once it's released
+        while !self.shared.queue.lock().is_empty() {
+            match self.shared.writer.try_lock() {
+                Some(mut writer) => {
+                    self.combine(&mut writer);
+                    flushed = true;
+                }
+                None => break,
+            }
+        }
+
+        flushed
+    }
+
+    /// Apply all queued operations and swap the buffers, waiting for any other flush to finish
+    pub fn flush(&self) {
+        let mut writer = self.shared.writer.lock();
+        self.combine(&mut writer);
+        drop(writer);
+
+        self.try_flush();
+    }
+
+    /// Lock the writer, blocking until any flush in progress completes
+    pub fn with_writer<R, F: FnOnce(&mut OpWriter<I, O>) -> R>(&self, f: F) -> R {
+        let output = f(&mut self.shared.writer.lock());
+
+        // pick up anything pushed while the writer was locked
+        self.try_flush();
+
+        output
+    }
+
+    fn combine(&self, writer: &mut OpWriter<I, O>) {
+        let ops = core::mem::take(&mut *self.shared.queue.lock());
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(ops = ops.len(), "combining queued operations");
+
+        writer.extend(ops);
+        writer.swap_buffers();
+    }
+}
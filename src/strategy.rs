@@ -1,9 +1,15 @@
 pub mod atomic;
 pub mod local;
+pub mod seqlock;
+pub mod sharded;
 
+#[cfg(feature = "alloc")]
+pub mod epoch;
 #[cfg(feature = "alloc")]
 pub mod hazard;
 #[cfg(feature = "alloc")]
+pub mod leak;
+#[cfg(feature = "alloc")]
 pub mod local_saving;
 
 #[cfg(feature = "alloc")]
@@ -14,5 +20,7 @@ pub mod saving_park {
     pub use super::saving::park::*;
 }
 
+#[cfg(feature = "metrics")]
+pub mod stats;
 #[cfg(feature = "std")]
 pub mod sync;
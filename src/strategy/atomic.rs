@@ -20,6 +20,19 @@ pub fn from_buffers<B>(front: B, back: B) -> (crate::base::Writer<Strong<B>>, cr
     )))
 }
 
+#[cfg(feature = "alloc")]
+pub fn from_snapshot<B: Clone>(snapshot: B) -> crate::base::Pair<Strong<B>> {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_snapshot(AtomicStrategy::default(), snapshot)))
+}
+
+#[cfg(feature = "alloc")]
+pub fn from_slices<B>(
+    front: std::vec::Vec<B>,
+    back: std::vec::Vec<B>,
+) -> crate::base::Pair<std::sync::Arc<crate::base::Inner<crate::raw::SliceBuffers<B>, AtomicStrategy>>> {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_slices(AtomicStrategy::default(), front, back)))
+}
+
 #[derive(Default)]
 pub struct AtomicStrategy {
     readers: AtomicUsize,
@@ -0,0 +1,186 @@
+use crate::{athin::Athin, traits::Strategy};
+use core::sync::atomic::{fence, AtomicBool, AtomicUsize, Ordering};
+#[cfg(feature = "std")]
+use parking_lot_core::SpinWait;
+use std::vec::Vec;
+
+#[cfg(feature = "std")]
+use parking_lot::Mutex;
+#[cfg(not(feature = "std"))]
+use spin::Mutex;
+
+// epochs are always odd, so they can never be confused with `UNPINNED`
+const UNPINNED: usize = 0;
+const FIRST_EPOCH: usize = 1;
+const EPOCH_STEP: usize = 2;
+
+#[cfg(feature = "alloc")]
+type Strong<B> = std::sync::Arc<crate::base::Inner<[B; 2], EpochStrategy>>;
+#[cfg(feature = "alloc")]
+type Weak<B> = std::sync::Weak<crate::base::Inner<[B; 2], EpochStrategy>>;
+
+#[cfg(feature = "alloc")]
+pub fn new<B: Default>() -> (crate::base::Writer<Strong<B>>, crate::base::Reader<Weak<B>>) {
+    from_buffers(B::default(), B::default())
+}
+
+#[cfg(feature = "alloc")]
+pub fn from_buffers<B>(front: B, back: B) -> (crate::base::Writer<Strong<B>>, crate::base::Reader<Weak<B>>) {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_raw_parts(
+        EpochStrategy::default(),
+        [front, back],
+    )))
+}
+
+#[cfg(feature = "alloc")]
+pub fn from_snapshot<B: Clone>(snapshot: B) -> crate::base::Pair<Strong<B>> {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_snapshot(EpochStrategy::default(), snapshot)))
+}
+
+#[cfg(feature = "alloc")]
+pub fn from_slices<B>(
+    front: std::vec::Vec<B>,
+    back: std::vec::Vec<B>,
+) -> crate::base::Pair<std::sync::Arc<crate::base::Inner<crate::raw::SliceBuffers<B>, EpochStrategy>>> {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_slices(EpochStrategy::default(), front, back)))
+}
+
+/// Pinning costs a load of the global epoch, a store to the reader's own slot and a `SeqCst`
+/// fence, readers never write to memory that other readers use
+pub struct EpochStrategy {
+    epoch: AtomicUsize,
+    tag_list: Mutex<Vec<Athin<AtomicUsize>>>,
+}
+
+impl Default for EpochStrategy {
+    fn default() -> Self {
+        Self {
+            epoch: AtomicUsize::new(FIRST_EPOCH),
+            tag_list: Mutex::default(),
+        }
+    }
+}
+
+pub struct RawGuard {
+    tag: Athin<AtomicUsize>,
+}
+
+pub struct FastCapture(());
+
+pub struct Capture {
+    epoch: usize,
+    active: Vec<Athin<AtomicUsize>>,
+    #[cfg(feature = "std")]
+    backoff: SpinWait,
+}
+
+pub struct ReaderTag(Athin<AtomicUsize>);
+pub struct WriterTag(());
+
+// if the reader pinned an epoch at or before the swap, it may still be reading the old buffer
+fn is_pinned_before(pinned: usize, epoch: usize) -> bool {
+    pinned != UNPINNED && epoch.wrapping_sub(pinned) as isize >= 0
+}
+
+unsafe impl Strategy for EpochStrategy {
+    type Which = AtomicBool;
+    type ReaderTag = ReaderTag;
+    type WriterTag = WriterTag;
+    type RawGuard = RawGuard;
+
+    type FastCapture = FastCapture;
+    type CaptureError = core::convert::Infallible;
+    type Capture = Capture;
+
+    unsafe fn dangling_reader_tag() -> Self::ReaderTag { ReaderTag(Athin::<AtomicUsize>::dangling()) }
+
+    #[inline]
+    unsafe fn reader_tag(&self) -> Self::ReaderTag {
+        let tag = Athin::new(AtomicUsize::new(UNPINNED));
+        self.tag_list.lock().push(tag.clone());
+        ReaderTag(tag)
+    }
+
+    #[inline]
+    unsafe fn writer_tag(&self) -> Self::WriterTag { WriterTag(()) }
+
+    #[inline]
+    fn try_capture_readers(&self, _: &mut Self::WriterTag) -> Result<Self::FastCapture, Self::CaptureError> {
+        Ok(FastCapture(()))
+    }
+
+    fn finish_capture_readers(&self, _: &mut Self::WriterTag, FastCapture(()): Self::FastCapture) -> Self::Capture {
+        // any reader that pins the new epoch is guaranteed to see the new value of `which`
+        let epoch = self.epoch.fetch_add(EPOCH_STEP, Ordering::Release);
+
+        // pairs with the fence in `begin_guard`, either the writer sees
+        // the pinned epoch, or the reader sees the new value of `which`
+        fence(Ordering::SeqCst);
+
+        let mut list = self.tag_list.lock();
+
+        let mut active = Vec::with_capacity(list.len().min(8));
+
+        // get rid of any dead readers and keep track of any active readers
+        list.retain(|tag| {
+            let is_alive = Athin::strong_count(tag) != 0;
+
+            if is_alive && is_pinned_before(tag.load(Ordering::Relaxed), epoch) {
+                active.push(tag.clone())
+            }
+
+            is_alive
+        });
+
+        Capture {
+            epoch,
+            active,
+            #[cfg(feature = "std")]
+            backoff: SpinWait::new(),
+        }
+    }
+
+    #[inline]
+    fn readers_have_exited(&self, capture: &mut Self::Capture) -> bool {
+        let epoch = capture.epoch;
+        capture
+            .active
+            .retain(|tag| is_pinned_before(tag.load(Ordering::Acquire), epoch));
+
+        capture.active.is_empty()
+    }
+
+    fn active_readers(&self, capture: &Self::Capture) -> Option<usize> { Some(capture.active.len()) }
+
+    #[cold]
+    #[cfg(feature = "std")]
+    fn pause(&self, capture: &mut Self::Capture) {
+        if !capture.backoff.spin() {
+            capture.backoff.reset()
+        }
+    }
+
+    #[inline]
+    fn begin_guard(&self, tag: &mut Self::ReaderTag) -> Self::RawGuard {
+        #[cold]
+        #[inline(never)]
+        fn begin_guard_fail() -> ! {
+            panic!("Previous reader guard was leaked");
+        }
+
+        if tag.0.load(Ordering::Relaxed) != UNPINNED {
+            begin_guard_fail()
+        }
+
+        tag.0.store(self.epoch.load(Ordering::Acquire), Ordering::Relaxed);
+
+        // pairs with the fence in `finish_capture_readers`, the pin must be visible
+        // to the writer before this reader loads `which`
+        fence(Ordering::SeqCst);
+
+        RawGuard { tag: tag.0.clone() }
+    }
+
+    #[inline]
+    unsafe fn end_guard(&self, guard: Self::RawGuard) { guard.tag.store(UNPINNED, Ordering::Release); }
+}
@@ -1,17 +1,27 @@
 mod queue;
+use core::task::Waker;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 
 use queue::{Queue, QueueNode};
 
-use crate::traits::Strategy;
+use crate::{
+    traits::{PauseKind, Strategy},
+    waker::WakerSlot,
+};
 #[cfg(feature = "std")]
 use parking_lot::{lock_api::RawMutex as _, Condvar, Mutex};
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "std")]
+const PARK_TIMEOUT: Duration = Duration::from_micros(100);
 
 #[cfg(not(feature = "std"))]
 pub struct HazardStrategy<W> {
     count: AtomicU32,
     queue: Queue<u32, true>,
     waiter: W,
+    waker: WakerSlot,
 }
 
 #[cfg(feature = "std")]
@@ -19,6 +29,7 @@ pub struct HazardStrategy<W = Waiter> {
     count: AtomicU32,
     queue: Queue<AtomicU32, true>,
     waiter: W,
+    waker: WakerSlot,
 }
 
 pub struct Spinner;
@@ -31,6 +42,11 @@ pub struct Waiter {
 pub trait Pause {
     fn pause(&self);
 
+    #[cfg(feature = "std")]
+    fn pause_until(&self, _deadline: Instant) { self.pause() }
+
+    fn kind(&self) -> PauseKind { PauseKind::Spin }
+
     fn notify(&self);
 }
 
@@ -43,10 +59,16 @@ impl Pause for Spinner {
 #[cfg(feature = "std")]
 impl Pause for Waiter {
     fn pause(&self) {
-        self.cv
-            .wait_for(&mut self.mx.lock(), std::time::Duration::from_micros(100));
+        self.cv.wait_for(&mut self.mx.lock(), PARK_TIMEOUT);
     }
 
+    fn pause_until(&self, deadline: Instant) {
+        let timeout = Instant::now() + PARK_TIMEOUT;
+        self.cv.wait_until(&mut self.mx.lock(), deadline.min(timeout));
+    }
+
+    fn kind(&self) -> PauseKind { PauseKind::Park }
+
     fn notify(&self) { self.cv.notify_one(); }
 }
 
@@ -75,6 +97,7 @@ impl<W> HazardStrategy<W> {
             count: AtomicU32::new(0),
             queue: Queue::new(),
             waiter,
+            waker: WakerSlot::new(),
         }
     }
 }
@@ -133,9 +156,17 @@ unsafe impl Strategy for HazardStrategy {
     }
 
     unsafe fn end_guard(&self, guard: Self::RawGuard) {
-        drop(guard);
+        drop(guard.0);
         self.waiter.notify();
+        self.waker.wake();
     }
 
     fn pause(&self, _: &mut Self::Capture) { self.waiter.pause() }
+
+    #[cfg(feature = "std")]
+    fn pause_until(&self, _: &mut Self::Capture, deadline: Instant) { self.waiter.pause_until(deadline) }
+
+    fn last_pause(&self, _: &Self::Capture) -> PauseKind { self.waiter.kind() }
+
+    fn register_waker(&self, _: &mut Self::Capture, waker: &Waker) { self.waker.register(waker) }
 }
@@ -36,7 +36,8 @@ impl<T, const SHOULD_DROP: bool> Drop for QueueNode<T, SHOULD_DROP> {
             self.ptr
                 .as_ref()
                 .has_both
-                .compare_exchange(true, false, Ordering::Release, Ordering::Relaxed)
+                // `SeqCst` so that `HazardStrategy::end_guard` can check for a waker without a fence
+                .compare_exchange(true, false, Ordering::SeqCst, Ordering::Relaxed)
                 .is_err()
                 && SHOULD_DROP
         } {
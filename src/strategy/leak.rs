@@ -0,0 +1,100 @@
+use std::boxed::Box;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeakAction {
+    /// The reader has held up the stall limit of swaps, and the writer will keep waiting
+    Reported,
+    /// The reader has held up the stall limit of swaps, and the writer reset its tag
+    Evicted,
+    /// The reader found that its previous guard was leaked when it started a new one
+    Recovered,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeakedReader {
+    /// Identifies the reader's tag, stable for the lifetime of the reader
+    pub id: usize,
+    /// How many swaps in a row found the reader still reading with an unchanged tag
+    pub stalls: usize,
+    pub action: LeakAction,
+}
+
+pub struct LeakPolicy<F: ?Sized = dyn Fn(LeakedReader) + Send + Sync> {
+    stall_limit: usize,
+    evict: bool,
+    on_leak: Option<Box<F>>,
+}
+
+impl<F: ?Sized> Default for LeakPolicy<F> {
+    /// Panic if a leaked guard is found, and wait on stalled readers forever
+    fn default() -> Self {
+        Self {
+            stall_limit: 0,
+            evict: false,
+            on_leak: None,
+        }
+    }
+}
+
+impl<F: ?Sized> LeakPolicy<F> {
+    /// Report readers that held up `swaps` swaps in a row without their tag changing, 0 disables this
+    pub fn stall_limit(mut self, swaps: usize) -> Self {
+        self.stall_limit = swaps;
+        self
+    }
+
+    /// Stop waiting on readers that reach the stall limit, and reset their tags
+    ///
+    /// # Safety
+    ///
+    /// Any reader that reaches the stall limit must have leaked its guard,
+    /// otherwise the writer may write to a buffer while it's being read
+    pub unsafe fn evict(mut self) -> Self {
+        self.evict = true;
+        self
+    }
+
+    // readers can always recover from their own leaked guards, but only do so if asked
+    pub(crate) fn recovers(&self) -> bool { self.stall_limit != 0 || self.on_leak.is_some() }
+
+    #[inline]
+    pub(crate) fn check(&self, stalls: usize) -> Option<LeakAction> {
+        if self.stall_limit == 0 || stalls < self.stall_limit {
+            None
+        } else if self.evict {
+            Some(LeakAction::Evicted)
+        } else {
+            Some(LeakAction::Reported)
+        }
+    }
+}
+
+impl<F: ?Sized + Fn(LeakedReader)> LeakPolicy<F> {
+    pub(crate) fn report(&self, reader: LeakedReader) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(reader = reader.id, stalls = reader.stalls, action = ?reader.action, "leaked reader guard");
+
+        if let Some(on_leak) = &self.on_leak {
+            on_leak(reader)
+        }
+    }
+}
+
+impl LeakPolicy {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn on_leak<G: Fn(LeakedReader) + Send + Sync + 'static>(mut self, on_leak: G) -> Self {
+        self.on_leak = Some(Box::new(on_leak));
+        self
+    }
+}
+
+impl LeakPolicy<dyn Fn(LeakedReader)> {
+    /// A policy for `LocalSavingStrategy`, which doesn't require the callback to be `Send + Sync`
+    pub fn local() -> Self { Self::default() }
+
+    pub fn on_leak<G: Fn(LeakedReader) + 'static>(mut self, on_leak: G) -> Self {
+        self.on_leak = Some(Box::new(on_leak));
+        self
+    }
+}
@@ -20,6 +20,19 @@ pub fn from_buffers<B>(front: B, back: B) -> (crate::base::Writer<Strong<B>>, cr
     )))
 }
 
+#[cfg(feature = "alloc")]
+pub fn from_snapshot<B: Clone>(snapshot: B) -> crate::base::Pair<Strong<B>> {
+    crate::base::new(std::rc::Rc::new(crate::base::Inner::from_snapshot(LocalStrategy::default(), snapshot)))
+}
+
+#[cfg(feature = "alloc")]
+pub fn from_slices<B>(
+    front: std::vec::Vec<B>,
+    back: std::vec::Vec<B>,
+) -> crate::base::Pair<std::rc::Rc<crate::base::Inner<crate::raw::SliceBuffers<B>, LocalStrategy>>> {
+    crate::base::new(std::rc::Rc::new(crate::base::Inner::from_slices(LocalStrategy::default(), front, back)))
+}
+
 #[derive(Default)]
 pub struct LocalStrategy {
     readers: Cell<usize>,
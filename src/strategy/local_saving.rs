@@ -1,4 +1,11 @@
-use crate::{thin::Thin, traits::Strategy};
+use crate::{
+    strategy::{
+        leak::{LeakAction, LeakPolicy, LeakedReader},
+        saving::{still_reading, too_many_shared_guards, DEPTH_MASK, EPOCH, MAX_DEPTH},
+    },
+    thin::Thin,
+    traits::{SharedStrategy, Strategy},
+};
 use core::cell::{Cell, UnsafeCell};
 use std::vec::Vec;
 
@@ -20,9 +27,23 @@ pub fn from_buffers<B>(front: B, back: B) -> (crate::base::Writer<Strong<B>>, cr
     )))
 }
 
+#[cfg(feature = "alloc")]
+pub fn from_snapshot<B: Clone>(snapshot: B) -> crate::base::Pair<Strong<B>> {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_snapshot(LocalSavingStrategy::default(), snapshot)))
+}
+
+#[cfg(feature = "alloc")]
+pub fn from_slices<B>(
+    front: std::vec::Vec<B>,
+    back: std::vec::Vec<B>,
+) -> crate::base::Pair<std::sync::Arc<crate::base::Inner<crate::raw::SliceBuffers<B>, LocalSavingStrategy>>> {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_slices(LocalSavingStrategy::default(), front, back)))
+}
+
 #[derive(Default)]
 pub struct LocalSavingStrategy {
-    tag_list: UnsafeCell<Vec<Thin<Cell<usize>>>>,
+    tag_list: UnsafeCell<Vec<TrackedReader>>,
+    leak_policy: LeakPolicy<dyn Fn(LeakedReader)>,
 }
 
 type Id = Thin<Cell<usize>>;
@@ -34,12 +55,75 @@ pub struct RawGuard {
 pub struct FastCapture(());
 
 pub struct Capture {
-    active: Vec<(usize, Id)>,
+    active: Vec<ActiveReader>,
+}
+
+struct ActiveReader {
+    value: usize,
+    stalls: usize,
+    checked: bool,
+    tag: Id,
+}
+
+// `blocked` is how many swaps in a row found the reader's tag at `last`, still reading
+struct TrackedReader {
+    tag: Id,
+    last: usize,
+    blocked: usize,
 }
 
 pub struct ReaderTag(Id);
 pub struct WriterTag(());
 
+fn reader_id(tag: &Id) -> usize { &**tag as *const Cell<usize> as usize }
+
+impl LocalSavingStrategy {
+    pub fn with_leak_policy(leak_policy: LeakPolicy<dyn Fn(LeakedReader)>) -> Self {
+        Self {
+            tag_list: UnsafeCell::new(Vec::new()),
+            leak_policy,
+        }
+    }
+
+    pub fn leak_policy(&self) -> &LeakPolicy<dyn Fn(LeakedReader)> { &self.leak_policy }
+
+    // returns true if the writer should keep waiting on the reader
+    #[cold]
+    #[inline(never)]
+    fn stalled(&self, reader: &ActiveReader, action: LeakAction) -> bool {
+        if action == LeakAction::Evicted {
+            reader.tag.set(reader.tag.get() & !DEPTH_MASK);
+        }
+
+        self.leak_policy.report(LeakedReader {
+            id: reader_id(&reader.tag),
+            stalls: reader.stalls,
+            action,
+        });
+
+        action == LeakAction::Reported
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn recover_leaked_guard(&self, tag: &Id) -> RawGuard {
+        if !self.leak_policy.recovers() {
+            panic!("Previous reader guard was leaked");
+        }
+
+        // the reader is borrowed mutably, so all of its other guards were leaked
+        tag.set((tag.get() & !DEPTH_MASK).wrapping_add(EPOCH + 1));
+
+        self.leak_policy.report(LeakedReader {
+            id: reader_id(tag),
+            stalls: 0,
+            action: LeakAction::Recovered,
+        });
+
+        RawGuard { tag: tag.clone() }
+    }
+}
+
 unsafe impl Strategy for LocalSavingStrategy {
     type Which = Cell<bool>;
     type ReaderTag = ReaderTag;
@@ -56,7 +140,11 @@ unsafe impl Strategy for LocalSavingStrategy {
     unsafe fn reader_tag(&self) -> Self::ReaderTag {
         let tag = Thin::new(Cell::new(0));
         let list = &mut *self.tag_list.get();
-        list.push(tag.clone());
+        list.push(TrackedReader {
+            tag: tag.clone(),
+            last: 0,
+            blocked: 0,
+        });
         ReaderTag(tag)
     }
 
@@ -74,16 +162,31 @@ unsafe impl Strategy for LocalSavingStrategy {
         let mut active = Vec::with_capacity(list.len().min(8));
 
         // get rid of any dead readers and keep track of any active readers
-        list.retain(|tag| {
-            let is_alive = Thin::strong_count(tag) != 0;
+        list.retain_mut(|reader| {
+            let is_alive = Thin::strong_count(&reader.tag) != 0;
 
-            let value = tag.get();
+            let value = reader.tag.get();
 
             // if the reader is alive and reading the value
-            if is_alive && value & 1 == 1 {
-                active.push((value, tag.clone()))
+            if is_alive && value & DEPTH_MASK != 0 {
+                // a reader that started a new guard since the last swap isn't stuck
+                reader.blocked = if value == reader.last {
+                    reader.blocked.wrapping_add(1)
+                } else {
+                    1
+                };
+                active.push(ActiveReader {
+                    value,
+                    stalls: reader.blocked,
+                    checked: false,
+                    tag: reader.tag.clone(),
+                })
+            } else {
+                reader.blocked = 0;
             }
 
+            reader.last = value;
+
             is_alive
         });
 
@@ -92,27 +195,45 @@ unsafe impl Strategy for LocalSavingStrategy {
 
     #[inline]
     fn readers_have_exited(&self, capture: &mut Self::Capture) -> bool {
-        capture.active.retain(|(old_value, tag)| *old_value == tag.get());
+        capture.active.retain_mut(|reader| {
+            if !still_reading(reader.value, reader.tag.get()) {
+                return false
+            }
+
+            // only check the policy once per swap, no matter how often the writer polls
+            if reader.checked {
+                return true
+            }
+
+            reader.checked = true;
+
+            #[cfg(feature = "tracing")]
+            tracing::debug!(reader = reader_id(&reader.tag), stalls = reader.stalls, "writer waiting on reader");
+
+            match self.leak_policy.check(reader.stalls) {
+                None => true,
+                Some(action) => self.stalled(reader, action),
+            }
+        });
 
         capture.active.is_empty()
     }
 
+    fn active_readers(&self, capture: &Self::Capture) -> Option<usize> { Some(capture.active.len()) }
+
+    fn reader_id(&self, tag: &Self::ReaderTag) -> Option<usize> { Some(reader_id(&tag.0)) }
+
     #[inline]
     fn begin_guard(&self, reader_tag: &mut Self::ReaderTag) -> Self::RawGuard {
-        #[cold]
-        #[inline(never)]
-        fn begin_guard_fail() -> ! {
-            panic!("Previous reader guard was leaked");
-        }
         let tag = &*reader_tag.0;
 
-        if tag.get() & 1 == 0 {
-            tag.set(tag.get().wrapping_add(1));
+        if tag.get() & DEPTH_MASK == 0 {
+            tag.set(tag.get().wrapping_add(EPOCH + 1));
             RawGuard {
                 tag: reader_tag.0.clone(),
             }
         } else {
-            begin_guard_fail()
+            self.recover_leaked_guard(&reader_tag.0)
         }
     }
 
@@ -120,6 +241,24 @@ unsafe impl Strategy for LocalSavingStrategy {
     unsafe fn end_guard(&self, guard: Self::RawGuard) {
         let tag = &*guard.tag;
         let value = tag.get();
-        tag.set(value.wrapping_add(1));
+        tag.set(value.wrapping_sub(1));
+    }
+}
+
+unsafe impl SharedStrategy for LocalSavingStrategy {
+    #[inline]
+    fn begin_shared_guard(&self, reader_tag: &Self::ReaderTag) -> Self::RawGuard {
+        let tag = &*reader_tag.0;
+        let value = tag.get();
+
+        match value & DEPTH_MASK {
+            0 => tag.set(value.wrapping_add(EPOCH + 1)),
+            depth if depth < MAX_DEPTH => tag.set(value + 1),
+            _ => too_many_shared_guards(),
+        }
+
+        RawGuard {
+            tag: reader_tag.0.clone(),
+        }
     }
 }
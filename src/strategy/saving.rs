@@ -1,5 +1,13 @@
-use crate::{athin::Athin, traits::Strategy};
-use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use crate::{
+    athin::Athin,
+    strategy::leak::{LeakAction, LeakPolicy, LeakedReader},
+    traits::{SharedStrategy, Strategy},
+    waker::WakerSlot,
+};
+use core::{
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    task::Waker,
+};
 #[cfg(feature = "std")]
 use parking_lot_core::SpinWait;
 use std::vec::Vec;
@@ -30,26 +38,170 @@ pub fn from_buffers<B>(front: B, back: B) -> (crate::base::Writer<Strong<B>>, cr
     )))
 }
 
+#[cfg(feature = "alloc")]
+pub fn from_snapshot<B: Clone>(snapshot: B) -> crate::base::Pair<Strong<B>> {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_snapshot(SavingStrategy::default(), snapshot)))
+}
+
+#[cfg(feature = "alloc")]
+pub fn from_slices<B>(
+    front: std::vec::Vec<B>,
+    back: std::vec::Vec<B>,
+) -> crate::base::Pair<std::sync::Arc<crate::base::Inner<crate::raw::SliceBuffers<B>, SavingStrategy>>> {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_slices(SavingStrategy::default(), front, back)))
+}
+
 #[derive(Default)]
 pub struct SavingStrategy {
-    tag_list: Mutex<Vec<Athin<AtomicUsize>>>,
+    tag_list: Mutex<Vec<TrackedReader>>,
+    // bumped on every swap, each reader counts its guards by the parity they started in
+    // so that the writer only waits on guards that started before it swapped
+    parity: AtomicUsize,
+    waker: WakerSlot,
+    leak_policy: LeakPolicy,
 }
 
 pub struct RawGuard {
-    tag: Athin<AtomicUsize>,
+    tag: Athin<Tag>,
+    parity: usize,
 }
 
 pub struct FastCapture(());
 
 pub struct Capture {
-    active: Vec<(usize, Athin<AtomicUsize>)>,
+    parity: usize,
+    active: Vec<ActiveReader>,
     #[cfg(feature = "std")]
     backoff: SpinWait,
 }
 
-pub struct ReaderTag(Athin<AtomicUsize>);
+struct ActiveReader {
+    value: usize,
+    stalls: usize,
+    checked: bool,
+    tag: Athin<Tag>,
+}
+
+// `blocked` is how many swaps in a row found the reader's tag at `last`, still reading
+struct TrackedReader {
+    tag: Athin<Tag>,
+    last: [usize; 2],
+    blocked: usize,
+}
+
+// one counter for each parity
+type Tag = [AtomicUsize; 2];
+
+pub struct ReaderTag(Athin<Tag>);
 pub struct WriterTag(());
 
+// a reader's tag holds the number of guards it has in the low bits, and an epoch
+// that changes when it starts reading in the high bits
+const DEPTH_BITS: u32 = 16;
+pub(crate) const EPOCH: usize = 1 << DEPTH_BITS;
+pub(crate) const DEPTH_MASK: usize = EPOCH - 1;
+pub(crate) const MAX_DEPTH: usize = DEPTH_MASK / 2;
+
+// is the reader still reading a value that it was reading when the tag was `old`
+#[inline]
+pub(crate) fn still_reading(old: usize, value: usize) -> bool {
+    value & DEPTH_MASK != 0 && (value ^ old) & !DEPTH_MASK == 0
+}
+
+#[cold]
+#[inline(never)]
+pub(crate) fn too_many_shared_guards() -> ! {
+    panic!("Too many shared reader guards");
+}
+
+fn reader_id(tag: &Athin<Tag>) -> usize { &**tag as *const Tag as usize }
+
+impl SavingStrategy {
+    pub fn with_leak_policy(leak_policy: LeakPolicy) -> Self {
+        Self {
+            tag_list: Mutex::new(Vec::new()),
+            parity: AtomicUsize::new(0),
+            waker: WakerSlot::new(),
+            leak_policy,
+        }
+    }
+
+    pub fn leak_policy(&self) -> &LeakPolicy { &self.leak_policy }
+
+    // returns true if the writer should keep waiting on the reader
+    #[cold]
+    #[inline(never)]
+    fn stalled(&self, parity: usize, reader: &ActiveReader, action: LeakAction) -> bool {
+        if action == LeakAction::Evicted {
+            if let Err(value) = reader.tag[parity].fetch_update(Ordering::Relaxed, Ordering::Relaxed, |value| {
+                Some(value & !DEPTH_MASK).filter(|_| still_reading(reader.value, value))
+            }) {
+                // the reader made progress, so it isn't stuck
+                return value & DEPTH_MASK != 0
+            }
+        }
+
+        self.leak_policy.report(LeakedReader {
+            id: reader_id(&reader.tag),
+            stalls: reader.stalls,
+            action,
+        });
+
+        action == LeakAction::Reported
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn recover_leaked_guards(&self, tag: &Athin<Tag>) {
+        if !self.leak_policy.recovers() {
+            panic!("Previous reader guard was leaked");
+        }
+
+        // the reader is borrowed mutably, so all of its other guards were leaked
+        for count in tag.iter() {
+            count.fetch_and(!DEPTH_MASK, Ordering::Relaxed);
+        }
+
+        self.leak_policy.report(LeakedReader {
+            id: reader_id(tag),
+            stalls: 0,
+            action: LeakAction::Recovered,
+        });
+    }
+
+    #[inline]
+    fn start_guard(&self, tag: &Athin<Tag>) -> RawGuard {
+        let mut parity = self.parity.load(Ordering::Relaxed) & 1;
+
+        loop {
+            // every guard bumps the epoch, so the writer can tell a reader that keeps
+            // starting new guards apart from a stuck one
+            let started = tag[parity].fetch_update(Ordering::SeqCst, Ordering::Relaxed, |value| {
+                Some(value.wrapping_add(EPOCH + 1)).filter(|_| value & DEPTH_MASK < MAX_DEPTH)
+            });
+
+            if started.is_err() {
+                too_many_shared_guards()
+            }
+
+            // if the writer swapped in the meantime it may have stopped waiting on this parity,
+            // so move over to the new one
+            let current = self.parity.load(Ordering::SeqCst) & 1;
+
+            if current == parity {
+                return RawGuard {
+                    tag: tag.clone(),
+                    parity,
+                }
+            }
+
+            tag[parity].fetch_sub(1, Ordering::SeqCst);
+            self.waker.wake();
+            parity = current;
+        }
+    }
+}
+
 unsafe impl Strategy for SavingStrategy {
     type Which = AtomicBool;
     type ReaderTag = ReaderTag;
@@ -60,12 +212,16 @@ unsafe impl Strategy for SavingStrategy {
     type CaptureError = core::convert::Infallible;
     type Capture = Capture;
 
-    unsafe fn dangling_reader_tag() -> Self::ReaderTag { ReaderTag(Athin::dangling()) }
+    unsafe fn dangling_reader_tag() -> Self::ReaderTag { ReaderTag(Athin::<Tag>::dangling()) }
 
     #[inline]
     unsafe fn reader_tag(&self) -> Self::ReaderTag {
-        let tag = Athin::new(AtomicUsize::new(0));
-        self.tag_list.lock().push(tag.clone());
+        let tag = Athin::new([AtomicUsize::new(0), AtomicUsize::new(0)]);
+        self.tag_list.lock().push(TrackedReader {
+            tag: tag.clone(),
+            last: [0; 2],
+            blocked: 0,
+        });
         ReaderTag(tag)
     }
 
@@ -74,30 +230,47 @@ unsafe impl Strategy for SavingStrategy {
 
     #[inline]
     fn try_capture_readers(&self, _: &mut Self::WriterTag) -> Result<Self::FastCapture, Self::CaptureError> {
-        core::sync::atomic::fence(Ordering::SeqCst);
         Ok(FastCapture(()))
     }
 
     fn finish_capture_readers(&self, _: &mut Self::WriterTag, FastCapture(()): Self::FastCapture) -> Self::Capture {
+        let parity = self.parity.fetch_add(1, Ordering::SeqCst) & 1;
+
         let mut list = self.tag_list.lock();
 
         let mut active = Vec::with_capacity(list.len().min(8));
 
         // get rid of any dead readers and keep track of any active readers
-        list.retain(|tag| {
-            let is_alive = Athin::strong_count(tag) != 0;
+        list.retain_mut(|reader| {
+            let is_alive = Athin::strong_count(&reader.tag) != 0;
 
-            let value = tag.load(Ordering::Acquire);
+            let value = reader.tag[parity].load(Ordering::SeqCst);
 
             // if the reader is alive and reading the value
-            if is_alive && value & 1 == 1 {
-                active.push((value, tag.clone()))
+            if is_alive && value & DEPTH_MASK != 0 {
+                // a reader that started a new guard since the last swap isn't stuck
+                reader.blocked = if value == reader.last[parity] {
+                    reader.blocked.wrapping_add(1)
+                } else {
+                    1
+                };
+                active.push(ActiveReader {
+                    value,
+                    stalls: reader.blocked,
+                    checked: false,
+                    tag: reader.tag.clone(),
+                })
+            } else {
+                reader.blocked = 0;
             }
 
+            reader.last[parity] = value;
+
             is_alive
         });
 
         Capture {
+            parity,
             active,
             #[cfg(feature = "std")]
             backoff: SpinWait::new(),
@@ -106,9 +279,30 @@ unsafe impl Strategy for SavingStrategy {
 
     #[inline]
     fn readers_have_exited(&self, capture: &mut Self::Capture) -> bool {
-        capture
-            .active
-            .retain(|(old_value, tag)| *old_value == tag.load(Ordering::Relaxed));
+        let parity = capture.parity;
+
+        capture.active.retain_mut(|reader| {
+            // guards that started after the swap count towards the other parity, so this only
+            // waits on guards that may still be reading the old buffer
+            if reader.tag[parity].load(Ordering::Relaxed) & DEPTH_MASK == 0 {
+                return false
+            }
+
+            // only check the policy once per swap, no matter how often the writer polls
+            if reader.checked {
+                return true
+            }
+
+            reader.checked = true;
+
+            #[cfg(feature = "tracing")]
+            tracing::debug!(reader = reader_id(&reader.tag), stalls = reader.stalls, "writer waiting on reader");
+
+            match self.leak_policy.check(reader.stalls) {
+                None => true,
+                Some(action) => self.stalled(parity, reader, action),
+            }
+        });
 
         let readers_have_exited = capture.active.is_empty();
 
@@ -127,20 +321,29 @@ unsafe impl Strategy for SavingStrategy {
         }
     }
 
+    fn register_waker(&self, _: &mut Self::Capture, waker: &Waker) { self.waker.register(waker) }
+
+    fn active_readers(&self, capture: &Self::Capture) -> Option<usize> { Some(capture.active.len()) }
+
+    fn reader_id(&self, tag: &Self::ReaderTag) -> Option<usize> { Some(reader_id(&tag.0)) }
+
     #[inline]
     fn begin_guard(&self, tag: &mut Self::ReaderTag) -> Self::RawGuard {
-        #[cold]
-        #[inline(never)]
-        fn begin_guard_fail() -> ! {
-            panic!("Previous reader guard was leaked");
-        }
-        if tag.0.fetch_add(1, Ordering::Acquire) & 1 == 0 {
-            RawGuard { tag: tag.0.clone() }
-        } else {
-            begin_guard_fail()
+        if tag.0.iter().any(|count| count.load(Ordering::Relaxed) & DEPTH_MASK != 0) {
+            self.recover_leaked_guards(&tag.0)
         }
+
+        self.start_guard(&tag.0)
     }
 
     #[inline]
-    unsafe fn end_guard(&self, guard: Self::RawGuard) { guard.tag.fetch_add(1, Ordering::Release); }
+    unsafe fn end_guard(&self, guard: Self::RawGuard) {
+        guard.tag[guard.parity].fetch_sub(1, Ordering::SeqCst);
+        self.waker.wake();
+    }
+}
+
+unsafe impl SharedStrategy for SavingStrategy {
+    #[inline]
+    fn begin_shared_guard(&self, tag: &Self::ReaderTag) -> Self::RawGuard { self.start_guard(&tag.0) }
 }
@@ -1,9 +1,15 @@
 use crate::{
-    strategy::saving::{Capture as RawCapture, FastCapture as RawFastCapture},
-    traits::Strategy,
+    strategy::{
+        leak::LeakPolicy,
+        saving::{Capture as RawCapture, FastCapture as RawFastCapture},
+    },
+    traits::{PauseKind, SharedStrategy, Strategy},
 };
-use core::sync::atomic::AtomicBool;
+use core::{sync::atomic::AtomicBool, task::Waker};
 use parking_lot::Condvar;
+use std::time::{Duration, Instant};
+
+const PARK_TIMEOUT: Duration = Duration::from_micros(100);
 
 #[cfg(feature = "alloc")]
 type Strong<B> = std::sync::Arc<crate::base::Inner<[B; 2], SavingParkStrategy>>;
@@ -23,6 +29,19 @@ pub fn from_buffers<B>(front: B, back: B) -> (crate::base::Writer<Strong<B>>, cr
     )))
 }
 
+#[cfg(feature = "alloc")]
+pub fn from_snapshot<B: Clone>(snapshot: B) -> crate::base::Pair<Strong<B>> {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_snapshot(SavingParkStrategy::default(), snapshot)))
+}
+
+#[cfg(feature = "alloc")]
+pub fn from_slices<B>(
+    front: std::vec::Vec<B>,
+    back: std::vec::Vec<B>,
+) -> crate::base::Pair<std::sync::Arc<crate::base::Inner<crate::raw::SliceBuffers<B>, SavingParkStrategy>>> {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_slices(SavingParkStrategy::default(), front, back)))
+}
+
 #[derive(Default)]
 pub struct SavingParkStrategy {
     raw: super::SavingStrategy,
@@ -30,18 +49,26 @@ pub struct SavingParkStrategy {
 }
 
 pub struct FastCapture(RawFastCapture);
-pub struct Capture(RawCapture);
+pub struct Capture(RawCapture, PauseKind);
 
 pub struct ReaderTag(super::ReaderTag);
 pub struct WriterTag(super::WriterTag);
 pub struct RawGuard(super::RawGuard);
 
 impl SavingParkStrategy {
+    pub fn with_leak_policy(leak_policy: LeakPolicy) -> Self {
+        Self {
+            raw: super::SavingStrategy::with_leak_policy(leak_policy),
+            cv: Condvar::new(),
+        }
+    }
+
+    pub fn leak_policy(&self) -> &LeakPolicy { self.raw.leak_policy() }
+
     #[cold]
     #[inline(never)]
-    fn park(&self) {
-        self.cv
-            .wait_for(&mut self.raw.tag_list.lock(), std::time::Duration::from_micros(100));
+    fn park(&self, timeout: Duration) {
+        self.cv.wait_for(&mut self.raw.tag_list.lock(), timeout);
     }
 }
 
@@ -77,21 +104,49 @@ unsafe impl Strategy for SavingParkStrategy {
         WriterTag(tag): &mut Self::WriterTag,
         FastCapture(capture): Self::FastCapture,
     ) -> Self::Capture {
-        Capture(self.raw.finish_capture_readers(tag, capture))
+        Capture(self.raw.finish_capture_readers(tag, capture), PauseKind::Spin)
     }
 
     #[inline]
-    fn readers_have_exited(&self, Capture(capture): &mut Self::Capture) -> bool {
+    fn readers_have_exited(&self, Capture(capture, _): &mut Self::Capture) -> bool {
         self.raw.readers_have_exited(capture)
     }
 
     #[cold]
-    fn pause(&self, Capture(capture): &mut Self::Capture) {
-        if !capture.backoff.spin() {
-            self.park();
+    fn pause(&self, Capture(capture, kind): &mut Self::Capture) {
+        *kind = if capture.backoff.spin() {
+            PauseKind::Spin
+        } else {
+            self.park(PARK_TIMEOUT);
+            PauseKind::Park
+        }
+    }
+
+    #[cold]
+    fn pause_until(&self, Capture(capture, kind): &mut Self::Capture, deadline: Instant) {
+        *kind = if capture.backoff.spin() {
+            PauseKind::Spin
+        } else {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            self.park(timeout.min(PARK_TIMEOUT));
+            PauseKind::Park
         }
     }
 
+    #[inline]
+    fn last_pause(&self, &Capture(_, kind): &Self::Capture) -> PauseKind { kind }
+
+    #[inline]
+    fn active_readers(&self, Capture(capture, _): &Self::Capture) -> Option<usize> { self.raw.active_readers(capture) }
+
+    #[inline]
+    fn reader_id(&self, ReaderTag(tag): &Self::ReaderTag) -> Option<usize> { self.raw.reader_id(tag) }
+
+    #[inline]
+    fn register_waker(&self, Capture(capture, _): &mut Self::Capture, waker: &Waker) {
+        self.raw.register_waker(capture, waker)
+    }
+
     #[inline]
     fn begin_guard(&self, ReaderTag(tag): &mut Self::ReaderTag) -> Self::RawGuard {
         RawGuard(self.raw.begin_guard(tag))
@@ -100,3 +155,10 @@ unsafe impl Strategy for SavingParkStrategy {
     #[inline]
     unsafe fn end_guard(&self, RawGuard(guard): Self::RawGuard) { self.raw.end_guard(guard) }
 }
+
+unsafe impl SharedStrategy for SavingParkStrategy {
+    #[inline]
+    fn begin_shared_guard(&self, ReaderTag(tag): &Self::ReaderTag) -> Self::RawGuard {
+        RawGuard(self.raw.begin_shared_guard(tag))
+    }
+}
@@ -0,0 +1,167 @@
+use core::{
+    mem::MaybeUninit,
+    sync::atomic::{fence, AtomicBool, AtomicUsize, Ordering},
+};
+
+use crate::{
+    base::{Inner, Reader},
+    traits::{Buffer, RawDoubleBuffer, Strategy, WeakBufferPtr},
+};
+
+#[cfg(feature = "alloc")]
+type Strong<B> = std::sync::Arc<crate::base::Inner<[B; 2], SeqLockStrategy>>;
+#[cfg(feature = "alloc")]
+type Weak<B> = std::sync::Weak<crate::base::Inner<[B; 2], SeqLockStrategy>>;
+
+#[cfg(feature = "alloc")]
+pub fn new<B: Default>() -> (crate::base::Writer<Strong<B>>, crate::base::Reader<Weak<B>>) {
+    from_buffers(B::default(), B::default())
+}
+
+#[cfg(feature = "alloc")]
+pub fn from_buffers<B>(front: B, back: B) -> (crate::base::Writer<Strong<B>>, crate::base::Reader<Weak<B>>) {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_raw_parts(
+        SeqLockStrategy::default(),
+        [front, back],
+    )))
+}
+
+#[cfg(feature = "alloc")]
+pub fn from_snapshot<B: Clone>(snapshot: B) -> crate::base::Pair<Strong<B>> {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_snapshot(SeqLockStrategy::default(), snapshot)))
+}
+
+#[cfg(feature = "alloc")]
+pub fn from_slices<B>(
+    front: std::vec::Vec<B>,
+    back: std::vec::Vec<B>,
+) -> crate::base::Pair<std::sync::Arc<crate::base::Inner<crate::raw::SliceBuffers<B>, SeqLockStrategy>>> {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_slices(SeqLockStrategy::default(), front, back)))
+}
+
+#[derive(Default)]
+pub struct SeqLockStrategy {
+    version: AtomicUsize,
+    // guards are counted by the parity of the version they started in,
+    // so the writer only waits on guards that started before it swapped
+    readers: [AtomicUsize; 2],
+}
+
+#[derive(Clone, Copy)]
+pub struct ReaderTag(());
+pub struct WriterTag(());
+pub struct RawGuard(usize);
+
+pub struct FastCapture(());
+pub struct Capture(usize);
+
+impl SeqLockStrategy {
+    fn load<R: ?Sized + RawDoubleBuffer>(&self, inner: &Inner<R, Self>) -> R::Buffer
+    where
+        R::Buffer: Copy,
+    {
+        loop {
+            let version = self.version.load(Ordering::Acquire);
+            let which = inner.which.load(Ordering::Acquire);
+
+            // the writer may be writing to this buffer if it swapped after `version` was loaded,
+            // so the value can't be trusted until the version is validated
+            let value = unsafe {
+                inner
+                    .raw
+                    .read(which)
+                    .cast::<MaybeUninit<R::Buffer>>()
+                    .read_volatile()
+            };
+
+            fence(Ordering::Acquire);
+
+            if self.version.load(Ordering::Relaxed) == version {
+                return unsafe { value.assume_init() }
+            }
+
+            core::hint::spin_loop();
+        }
+    }
+}
+
+impl<I: WeakBufferPtr<Strategy = SeqLockStrategy>> Reader<I>
+where
+    I::Raw: Copy,
+    Buffer<I::Strong>: Copy,
+{
+    pub fn load(&self) -> Buffer<I::Strong> { self.try_load().expect("Tried to read from a dangling `Reader<B>`") }
+
+    pub fn try_load(&self) -> Result<Buffer<I::Strong>, I::UpgradeError> {
+        let weak = self.as_weak();
+
+        if weak.is_dangling() {
+            weak.upgrade()?;
+        }
+
+        // the reader keeps `Inner` allocated, and the buffers are `Copy` so they are still
+        // valid to read if the writer drops `Inner` in the meantime
+        let inner = unsafe { &*weak.as_ptr() };
+        Ok(inner.strategy.load(inner))
+    }
+}
+
+unsafe impl Strategy for SeqLockStrategy {
+    type Which = AtomicBool;
+    type ReaderTag = ReaderTag;
+    type WriterTag = WriterTag;
+    type RawGuard = RawGuard;
+
+    type FastCapture = FastCapture;
+    type Capture = Capture;
+    type CaptureError = core::convert::Infallible;
+
+    unsafe fn dangling_reader_tag() -> Self::ReaderTag { ReaderTag(()) }
+
+    unsafe fn reader_tag(&self) -> Self::ReaderTag { ReaderTag(()) }
+
+    unsafe fn writer_tag(&self) -> Self::WriterTag { WriterTag(()) }
+
+    fn try_capture_readers(&self, _: &mut Self::WriterTag) -> Result<Self::FastCapture, Self::CaptureError> {
+        Ok(FastCapture(()))
+    }
+
+    fn finish_capture_readers(&self, _: &mut Self::WriterTag, FastCapture(()): Self::FastCapture) -> Self::Capture {
+        let version = self.version.fetch_add(1, Ordering::SeqCst);
+
+        // any writes to the old read buffer must happen after the version is bumped
+        fence(Ordering::Release);
+
+        Capture(version & 1)
+    }
+
+    // only guards block the writer, optimistic loads never do
+    fn readers_have_exited(&self, &mut Capture(parity): &mut Self::Capture) -> bool {
+        self.readers[parity].load(Ordering::SeqCst) == 0
+    }
+
+    fn pause(&self, _: &mut Self::Capture) { core::hint::spin_loop() }
+
+    fn begin_guard(&self, _: &mut Self::ReaderTag) -> Self::RawGuard {
+        let mut parity = self.version.load(Ordering::Relaxed) & 1;
+
+        loop {
+            self.readers[parity]
+                .fetch_update(Ordering::SeqCst, Ordering::Relaxed, |readers| readers.checked_add(1))
+                .expect("Tried to create too many reader guards");
+
+            // if the writer swapped in the meantime it may have stopped waiting on this parity,
+            // so move over to the new one
+            let current = self.version.load(Ordering::SeqCst) & 1;
+
+            if current == parity {
+                return RawGuard(parity)
+            }
+
+            self.readers[parity].fetch_sub(1, Ordering::Release);
+            parity = current;
+        }
+    }
+
+    unsafe fn end_guard(&self, RawGuard(parity): Self::RawGuard) { self.readers[parity].fetch_sub(1, Ordering::Release); }
+}
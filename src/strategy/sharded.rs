@@ -0,0 +1,162 @@
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crossbeam_utils::CachePadded;
+#[cfg(feature = "std")]
+use parking_lot_core::SpinWait;
+
+use crate::traits::Strategy;
+
+#[cfg(feature = "alloc")]
+type Strong<B, const N: usize> = std::sync::Arc<crate::base::Inner<[B; 2], ShardedStrategy<N>>>;
+#[cfg(feature = "alloc")]
+type Weak<B, const N: usize> = std::sync::Weak<crate::base::Inner<[B; 2], ShardedStrategy<N>>>;
+
+#[cfg(feature = "alloc")]
+pub fn new<B: Default, const N: usize>() -> (crate::base::Writer<Strong<B, N>>, crate::base::Reader<Weak<B, N>>) {
+    from_buffers(B::default(), B::default())
+}
+
+#[cfg(feature = "alloc")]
+pub fn from_buffers<B, const N: usize>(
+    front: B,
+    back: B,
+) -> (crate::base::Writer<Strong<B, N>>, crate::base::Reader<Weak<B, N>>) {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_raw_parts(
+        ShardedStrategy::INIT,
+        [front, back],
+    )))
+}
+
+#[cfg(feature = "alloc")]
+pub fn from_snapshot<B: Clone, const N: usize>(snapshot: B) -> crate::base::Pair<Strong<B, N>> {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_snapshot(ShardedStrategy::INIT, snapshot)))
+}
+
+#[cfg(feature = "alloc")]
+pub fn from_slices<B, const N: usize>(
+    front: std::vec::Vec<B>,
+    back: std::vec::Vec<B>,
+) -> crate::base::Pair<std::sync::Arc<crate::base::Inner<crate::raw::SliceBuffers<B>, ShardedStrategy<N>>>> {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_slices(ShardedStrategy::INIT, front, back)))
+}
+
+pub struct ShardedStrategy<const N: usize> {
+    next_shard: AtomicUsize,
+    // bumped on every swap, each shard counts its readers by the parity they started in
+    // so that the writer only waits on readers that started before it swapped
+    parity: AtomicUsize,
+    shards: [CachePadded<[AtomicUsize; 2]>; N],
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_SHARD: CachePadded<[AtomicUsize; 2]> = CachePadded::new([AtomicUsize::new(0), AtomicUsize::new(0)]);
+
+#[allow(clippy::declare_interior_mutable_const)]
+impl<const N: usize> ShardedStrategy<N> {
+    pub const INIT: Self = {
+        assert!(N != 0, "`ShardedStrategy` needs at least one shard");
+
+        Self {
+            next_shard: AtomicUsize::new(0),
+            parity: AtomicUsize::new(0),
+            shards: [EMPTY_SHARD; N],
+        }
+    };
+
+    fn readers(&self, parity: usize) -> usize {
+        self.shards
+            .iter()
+            .fold(0, |readers, shard| readers.wrapping_add(shard[parity].load(Ordering::SeqCst)))
+    }
+}
+
+impl<const N: usize> Default for ShardedStrategy<N> {
+    fn default() -> Self { Self::INIT }
+}
+
+#[derive(Clone, Copy)]
+pub struct ReaderTag(usize);
+pub struct WriterTag(());
+pub struct RawGuard(usize, usize);
+
+pub struct FastCapture(());
+pub struct Capture {
+    parity: usize,
+    #[cfg(feature = "std")]
+    backoff: SpinWait,
+}
+#[derive(Debug)]
+pub struct CaptureError(());
+
+unsafe impl<const N: usize> Strategy for ShardedStrategy<N> {
+    type Which = AtomicBool;
+    type ReaderTag = ReaderTag;
+    type WriterTag = WriterTag;
+    type RawGuard = RawGuard;
+
+    type FastCapture = FastCapture;
+    type Capture = Capture;
+    type CaptureError = CaptureError;
+
+    unsafe fn dangling_reader_tag() -> Self::ReaderTag { ReaderTag(0) }
+
+    unsafe fn reader_tag(&self) -> Self::ReaderTag { ReaderTag(self.next_shard.fetch_add(1, Ordering::Relaxed) % N) }
+
+    unsafe fn writer_tag(&self) -> Self::WriterTag { WriterTag(()) }
+
+    fn try_capture_readers(&self, _: &mut Self::WriterTag) -> Result<Self::FastCapture, Self::CaptureError> {
+        if self.readers(0) == 0 && self.readers(1) == 0 {
+            Ok(FastCapture(()))
+        } else {
+            Err(CaptureError(()))
+        }
+    }
+
+    fn finish_capture_readers(&self, _: &mut Self::WriterTag, FastCapture(()): Self::FastCapture) -> Self::Capture {
+        Capture {
+            parity: self.parity.fetch_add(1, Ordering::SeqCst) & 1,
+            #[cfg(feature = "std")]
+            backoff: SpinWait::new(),
+        }
+    }
+
+    // the shards can't be summed atomically, so a reader may have entered after they were
+    // checked but before `which` was swapped, readers that start after the swap aren't waited on
+    fn readers_have_exited(&self, capture: &mut Self::Capture) -> bool { self.readers(capture.parity) == 0 }
+
+    #[cold]
+    fn pause(&self, _capture: &mut Self::Capture) {
+        #[cfg(feature = "std")]
+        if !_capture.backoff.spin() {
+            _capture.backoff.reset()
+        }
+
+        #[cfg(not(feature = "std"))]
+        core::hint::spin_loop();
+    }
+
+    fn begin_guard(&self, &mut ReaderTag(shard): &mut Self::ReaderTag) -> Self::RawGuard {
+        let mut parity = self.parity.load(Ordering::Relaxed) & 1;
+
+        loop {
+            self.shards[shard][parity]
+                .fetch_update(Ordering::SeqCst, Ordering::Relaxed, |readers| readers.checked_add(1))
+                .expect("Tried to create too many reader guards");
+
+            // if the writer swapped in the meantime it may have stopped waiting on this parity,
+            // so move over to the new one
+            let current = self.parity.load(Ordering::SeqCst) & 1;
+
+            if current == parity {
+                return RawGuard(shard, parity)
+            }
+
+            self.shards[shard][parity].fetch_sub(1, Ordering::Release);
+            parity = current;
+        }
+    }
+
+    unsafe fn end_guard(&self, RawGuard(shard, parity): Self::RawGuard) {
+        self.shards[shard][parity].fetch_sub(1, Ordering::Release);
+    }
+}
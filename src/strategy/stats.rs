@@ -0,0 +1,176 @@
+use core::{
+    convert::TryFrom,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+    task::Waker,
+};
+use std::time::{Duration, Instant};
+
+use crate::traits::{PauseKind, SharedStrategy, Strategy};
+
+// bucket `i` counts waits shorter than `2^i` microseconds, the last bucket counts everything else
+pub const HISTOGRAM_BUCKETS: usize = 16;
+
+#[derive(Default)]
+pub struct StrategyStats<S> {
+    inner: S,
+    swaps: AtomicU64,
+    spins: AtomicU64,
+    parks: AtomicU64,
+    max_active_readers: AtomicUsize,
+    wait_histogram: [AtomicU64; HISTOGRAM_BUCKETS],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub swaps: u64,
+    pub spins: u64,
+    pub parks: u64,
+    pub max_active_readers: usize,
+    pub wait_histogram: [u64; HISTOGRAM_BUCKETS],
+}
+
+pub struct Capture<C> {
+    inner: C,
+    // when the writer first found readers that hadn't exited
+    waiting_since: Option<Instant>,
+    // how long the writer waited, set when it first finds that the readers have exited
+    waited: Option<Duration>,
+}
+
+impl<S> StrategyStats<S> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            swaps: AtomicU64::new(0),
+            spins: AtomicU64::new(0),
+            parks: AtomicU64::new(0),
+            max_active_readers: AtomicUsize::new(0),
+            wait_histogram: Default::default(),
+        }
+    }
+
+    pub fn inner(&self) -> &S { &self.inner }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            swaps: self.swaps.load(Ordering::Relaxed),
+            spins: self.spins.load(Ordering::Relaxed),
+            parks: self.parks.load(Ordering::Relaxed),
+            max_active_readers: self.max_active_readers.load(Ordering::Relaxed),
+            wait_histogram: self.wait_histogram.each_ref().map(|bucket| bucket.load(Ordering::Relaxed)),
+        }
+    }
+
+    fn record_pause(&self, kind: PauseKind) {
+        match kind {
+            PauseKind::Spin => self.spins.fetch_add(1, Ordering::Relaxed),
+            PauseKind::Park => self.parks.fetch_add(1, Ordering::Relaxed),
+        };
+    }
+
+    fn record_wait(&self, wait: Duration) {
+        let micros = u64::try_from(wait.as_micros()).unwrap_or(u64::MAX);
+        let bucket = (u64::BITS - micros.leading_zeros()) as usize;
+        self.wait_histogram[bucket.min(HISTOGRAM_BUCKETS - 1)].fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Stats {
+    pub fn total_waits(&self) -> u64 { self.wait_histogram.iter().sum() }
+}
+
+unsafe impl<S: Strategy> Strategy for StrategyStats<S> {
+    type Which = S::Which;
+    type ReaderTag = S::ReaderTag;
+    type WriterTag = S::WriterTag;
+    type RawGuard = S::RawGuard;
+
+    type FastCapture = S::FastCapture;
+    type CaptureError = S::CaptureError;
+    type Capture = Capture<S::Capture>;
+
+    unsafe fn dangling_reader_tag() -> Self::ReaderTag { S::dangling_reader_tag() }
+
+    #[inline]
+    unsafe fn reader_tag(&self) -> Self::ReaderTag { self.inner.reader_tag() }
+
+    #[inline]
+    unsafe fn writer_tag(&self) -> Self::WriterTag { self.inner.writer_tag() }
+
+    #[inline]
+    fn try_capture_readers(&self, tag: &mut Self::WriterTag) -> Result<Self::FastCapture, Self::CaptureError> {
+        self.inner.try_capture_readers(tag)
+    }
+
+    fn finish_capture_readers(&self, tag: &mut Self::WriterTag, capture: Self::FastCapture) -> Self::Capture {
+        let capture = self.inner.finish_capture_readers(tag, capture);
+
+        self.swaps.fetch_add(1, Ordering::Relaxed);
+
+        if let Some(active) = self.inner.active_readers(&capture) {
+            self.max_active_readers.fetch_max(active, Ordering::Relaxed);
+        }
+
+        Capture {
+            inner: capture,
+            waiting_since: None,
+            waited: None,
+        }
+    }
+
+    // only time spent waiting on readers is recorded, not the time until the writer
+    // comes back to finish the swap
+    #[inline]
+    fn readers_have_exited(&self, capture: &mut Self::Capture) -> bool {
+        let readers_have_exited = self.inner.readers_have_exited(&mut capture.inner);
+
+        if readers_have_exited {
+            if capture.waited.is_none() {
+                capture.waited = Some(capture.waiting_since.map_or(Duration::ZERO, |since| since.elapsed()));
+            }
+        } else if capture.waiting_since.is_none() {
+            capture.waiting_since = Some(Instant::now());
+        }
+
+        readers_have_exited
+    }
+
+    fn finish_capture(&self, tag: &Self::WriterTag, capture: Self::Capture) {
+        self.record_wait(capture.waited.unwrap_or(Duration::ZERO));
+        self.inner.finish_capture(tag, capture.inner)
+    }
+
+    #[inline]
+    fn active_readers(&self, capture: &Self::Capture) -> Option<usize> { self.inner.active_readers(&capture.inner) }
+
+    fn reader_id(&self, tag: &Self::ReaderTag) -> Option<usize> { self.inner.reader_id(tag) }
+
+    fn pause(&self, capture: &mut Self::Capture) {
+        self.inner.pause(&mut capture.inner);
+        self.record_pause(self.inner.last_pause(&capture.inner));
+    }
+
+    fn pause_until(&self, capture: &mut Self::Capture, deadline: Instant) {
+        self.inner.pause_until(&mut capture.inner, deadline);
+        self.record_pause(self.inner.last_pause(&capture.inner));
+    }
+
+    #[inline]
+    fn last_pause(&self, capture: &Self::Capture) -> PauseKind { self.inner.last_pause(&capture.inner) }
+
+    #[inline]
+    fn register_waker(&self, capture: &mut Self::Capture, waker: &Waker) {
+        self.inner.register_waker(&mut capture.inner, waker)
+    }
+
+    #[inline]
+    fn begin_guard(&self, tag: &mut Self::ReaderTag) -> Self::RawGuard { self.inner.begin_guard(tag) }
+
+    #[inline]
+    unsafe fn end_guard(&self, guard: Self::RawGuard) { self.inner.end_guard(guard) }
+}
+
+unsafe impl<S: SharedStrategy> SharedStrategy for StrategyStats<S> {
+    #[inline]
+    fn begin_shared_guard(&self, tag: &Self::ReaderTag) -> Self::RawGuard { self.inner.begin_shared_guard(tag) }
+}
@@ -1,6 +1,13 @@
-use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-
-use crate::traits::Strategy;
+use core::{
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    task::Waker,
+};
+
+use crate::{
+    traits::{PauseKind, Strategy},
+    waker::WakerSlot,
+};
+use std::time::Instant;
 use parking_lot_core::{park, unpark_all, unpark_filter, FilterOp, ParkToken, SpinWait, UnparkToken};
 
 const SWAP_TOKEN: ParkToken = ParkToken(0);
@@ -30,16 +37,31 @@ pub fn from_buffers<B>(front: B, back: B) -> (crate::base::Writer<Strong<B>>, cr
     )))
 }
 
+#[cfg(feature = "alloc")]
+pub fn from_snapshot<B: Clone>(snapshot: B) -> crate::base::Pair<Strong<B>> {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_snapshot(SyncStrategy::INIT, snapshot)))
+}
+
+#[cfg(feature = "alloc")]
+pub fn from_slices<B>(
+    front: std::vec::Vec<B>,
+    back: std::vec::Vec<B>,
+) -> crate::base::Pair<std::sync::Arc<crate::base::Inner<crate::raw::SliceBuffers<B>, SyncStrategy>>> {
+    crate::base::new(std::sync::Arc::new(crate::base::Inner::from_slices(SyncStrategy::INIT, front, back)))
+}
+
 pub struct SyncStrategy {
     lock: AtomicUsize,
+    waker: WakerSlot,
 }
 
-fn timeout() -> std::time::Instant { std::time::Instant::now() + std::time::Duration::from_micros(100) }
+fn timeout() -> Instant { Instant::now() + std::time::Duration::from_micros(100) }
 
 #[allow(clippy::declare_interior_mutable_const)]
 impl SyncStrategy {
     pub const INIT: Self = Self {
         lock: AtomicUsize::new(0),
+        waker: WakerSlot::new(),
     };
 }
 
@@ -76,30 +98,25 @@ unsafe impl Strategy for SyncStrategy {
 
     fn finish_capture_readers(&self, _: &mut Self::WriterTag, _: Self::FastCapture) -> Self::Capture { Capture(()) }
 
-    fn readers_have_exited(&self, _: &mut Self::Capture) -> bool { self.lock.load(Ordering::Acquire) == 0 }
+    fn readers_have_exited(&self, _: &mut Self::Capture) -> bool {
+        self.lock.load(Ordering::Acquire) & !PENDING_SWAP == 0
+    }
 
     fn finish_capture(&self, _: &Self::WriterTag, _: Self::Capture) {
-        let lock = self.lock.fetch_and(!PENDING_READERS, Ordering::AcqRel);
+        let lock = self.lock.fetch_and(!(PENDING_SWAP | PENDING_READERS), Ordering::AcqRel);
 
         if lock != 0 {
             self.finish_capture_slow()
         }
     }
 
-    fn pause(&self, _: &mut Self::Capture) {
-        self.lock.fetch_or(PENDING_SWAP, Ordering::Release);
+    fn pause(&self, _: &mut Self::Capture) { self.park_writer(timeout()) }
 
-        let key = self as *const _ as usize;
-        let validate = || self.lock.load(Ordering::Acquire) & !PENDING_SWAP != 0;
-        let before_sleep = || {};
-        let timed_out = |_, _| {};
+    fn pause_until(&self, _: &mut Self::Capture, deadline: Instant) { self.park_writer(deadline.min(timeout())) }
 
-        unsafe {
-            park(key, validate, before_sleep, timed_out, SWAP_TOKEN, Some(timeout()));
-        }
+    fn last_pause(&self, _: &Self::Capture) -> PauseKind { PauseKind::Park }
 
-        self.lock.fetch_and(!PENDING_SWAP, Ordering::Release);
-    }
+    fn register_waker(&self, _: &mut Self::Capture, waker: &Waker) { self.waker.register(waker) }
 
     fn begin_guard(&self, _: &mut Self::ReaderTag) -> Self::RawGuard {
         let should_wait = self
@@ -122,7 +139,9 @@ unsafe impl Strategy for SyncStrategy {
 
     unsafe fn end_guard(&self, _: Self::RawGuard) {
         // if last reader and there is a pending swap
-        let pending_swap = self.lock.fetch_sub(ONE_READER, Ordering::Release) != PENDING_SWAP | ONE_READER;
+        let pending_swap = self.lock.fetch_sub(ONE_READER, Ordering::SeqCst) == PENDING_SWAP | ONE_READER;
+        self.waker.wake();
+
         if pending_swap {
             self.end_guard_slow()
         }
@@ -130,6 +149,21 @@ unsafe impl Strategy for SyncStrategy {
 }
 
 impl SyncStrategy {
+    fn park_writer(&self, timeout: Instant) {
+        self.lock.fetch_or(PENDING_SWAP, Ordering::Release);
+
+        let key = self as *const _ as usize;
+        let validate = || self.lock.load(Ordering::Acquire) & !PENDING_SWAP != 0;
+        let before_sleep = || {};
+        let timed_out = |_, _| {};
+
+        unsafe {
+            park(key, validate, before_sleep, timed_out, SWAP_TOKEN, Some(timeout));
+        }
+
+        self.lock.fetch_and(!PENDING_SWAP, Ordering::Release);
+    }
+
     #[cold]
     #[inline(never)]
     fn finish_capture_slow(&self) {
@@ -1,26 +1,63 @@
-use core::{cell::Cell, marker::PhantomData, ops::Deref, ptr::NonNull};
+use core::{cell::Cell, marker::PhantomData, mem::ManuallyDrop, ops::Deref, ptr::NonNull};
 use std::boxed::Box;
 
+// the counts are stored off by one, so the strong count wraps around to this once the last strong is dropped
+const DEAD: usize = usize::MAX;
+
 pub struct Thin<T: ?Sized> {
     inner: NonNull<ThinInner<T>>,
     drop: PhantomData<T>,
 }
 
+pub struct ThinWeak<T: ?Sized> {
+    inner: NonNull<ThinInner<T>>,
+    drop: PhantomData<T>,
+}
+
 #[repr(C)]
 pub struct ThinInner<T: ?Sized> {
     count: Cell<usize>,
-    value: T,
+    // all strong pointers collectively hold a single weak pointer
+    weak: Cell<usize>,
+    value: ManuallyDrop<T>,
 }
 
 impl<T> ThinInner<T> {
     pub fn new(value: T) -> Self {
         Self {
             count: Cell::new(0),
-            value,
+            weak: Cell::new(0),
+            value: ManuallyDrop::new(value),
         }
     }
 }
 
+// only runs for an inner that never became a `Thin`, `drop_weak` frees the allocation without it
+impl<T: ?Sized> Drop for ThinInner<T> {
+    fn drop(&mut self) { unsafe { ManuallyDrop::drop(&mut self.value) } }
+}
+
+fn increment(count: &Cell<usize>) {
+    count.set(
+        count
+            .get()
+            .checked_add(1)
+            .filter(|&count| count != DEAD)
+            .expect("tried to clone a local thin too many times"),
+    );
+}
+
+unsafe fn drop_weak<T: ?Sized>(inner: NonNull<ThinInner<T>>) {
+    let weak = &inner.as_ref().weak;
+    let old = weak.get();
+    weak.set(old.wrapping_sub(1));
+
+    if old == 0 {
+        // the value was already dropped along with the last strong pointer
+        drop(Box::from_raw(inner.as_ptr() as *mut ManuallyDrop<ThinInner<T>>));
+    }
+}
+
 impl Thin<Cell<usize>> {
     pub(crate) fn dangling() -> Self {
         #[cfg(feature = "std")]
@@ -44,6 +81,34 @@ impl<T> Thin<T> {
 
 impl<T: ?Sized> Thin<T> {
     pub fn strong_count(&self) -> usize { unsafe { self.inner.as_ref().count.get() } }
+
+    pub fn downgrade(this: &Self) -> ThinWeak<T> {
+        unsafe { increment(&this.inner.as_ref().weak) }
+
+        ThinWeak {
+            inner: this.inner,
+            drop: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized> ThinWeak<T> {
+    pub fn as_ptr(&self) -> *const T { unsafe { core::ptr::addr_of!((*self.inner.as_ptr()).value) as *const T } }
+
+    pub fn is_dangling(&self) -> bool { unsafe { self.inner.as_ref().count.get() == DEAD } }
+
+    pub fn upgrade(&self) -> Option<Thin<T>> {
+        if self.is_dangling() {
+            return None
+        }
+
+        unsafe { increment(&self.inner.as_ref().count) }
+
+        Some(Thin {
+            inner: self.inner,
+            drop: PhantomData,
+        })
+    }
 }
 
 impl<T: ?Sized> From<Box<ThinInner<T>>> for Thin<T> {
@@ -58,15 +123,18 @@ impl<T: ?Sized> From<Box<ThinInner<T>>> for Thin<T> {
 
 impl<T: ?Sized> Clone for Thin<T> {
     fn clone(&self) -> Self {
-        unsafe {
-            let count = &self.inner.as_ref().count;
-            count.set(
-                count
-                    .get()
-                    .checked_add(1)
-                    .expect("tried to clone a local thin too many times"),
-            );
+        unsafe { increment(&self.inner.as_ref().count) }
+
+        Self {
+            inner: self.inner,
+            drop: PhantomData,
         }
+    }
+}
+
+impl<T: ?Sized> Clone for ThinWeak<T> {
+    fn clone(&self) -> Self {
+        unsafe { increment(&self.inner.as_ref().weak) }
 
         Self {
             inner: self.inner,
@@ -85,12 +153,17 @@ impl<T: ?Sized> Drop for Thin<T> {
         };
         if count == 0 {
             unsafe {
-                Box::from_raw(self.inner.as_ptr());
+                ManuallyDrop::drop(&mut (*self.inner.as_ptr()).value);
+                drop_weak(self.inner);
             }
         }
     }
 }
 
+impl<T: ?Sized> Drop for ThinWeak<T> {
+    fn drop(&mut self) { unsafe { drop_weak(self.inner) } }
+}
+
 impl<T: ?Sized> Deref for Thin<T> {
     type Target = T;
 
@@ -1,4 +1,4 @@
-use core::{cell::Cell, ops::Deref, sync::atomic::AtomicBool};
+use core::{cell::Cell, ops::Deref, sync::atomic::AtomicBool, task::Waker};
 
 use crate::base::Inner;
 use radium::Radium;
@@ -44,6 +44,12 @@ pub unsafe trait WeakBuffer: Clone {
     fn upgrade(&self) -> Result<Self::Strong, Self::UpgradeError>;
 }
 
+/// A `WeakBuffer` that can reach `Inner` without upgrading
+pub unsafe trait WeakBufferPtr: WeakBuffer {
+    // the `Inner` must stay allocated as long as `self` is alive, even after it's dropped
+    fn as_ptr(&self) -> *const Inner<Self::Raw, Self::Strategy>;
+}
+
 pub unsafe trait Strategy {
     type Which: TrustedRadium<Item = bool>;
     type ReaderTag;
@@ -68,20 +74,66 @@ pub unsafe trait Strategy {
 
     fn finish_capture(&self, _: &Self::WriterTag, _capture: Self::Capture) {}
 
+    /// Identifies the reader that owns `tag`, if its tag has an identity,
+    /// this is the same id that the strategy reports for leaked readers
+    #[inline]
+    fn reader_id(&self, _: &Self::ReaderTag) -> Option<usize> { None }
+
+    /// The number of readers that the writer is still waiting on, if the strategy tracks them
+    #[inline]
+    fn active_readers(&self, _: &Self::Capture) -> Option<usize> { None }
+
     #[inline]
     fn pause(&self, _: &mut Self::Capture) {}
 
+    /// Like `pause`, but shouldn't block past the `deadline`
+    #[inline]
+    #[cfg(feature = "std")]
+    fn pause_until(&self, capture: &mut Self::Capture, _deadline: std::time::Instant) { self.pause(capture) }
+
+    /// How the last call to `pause` or `pause_until` waited
+    #[inline]
+    fn last_pause(&self, _: &Self::Capture) -> PauseKind { PauseKind::Spin }
+
+    /// Register a waker that will be woken when readers exit
+    ///
+    /// The caller must check `readers_have_exited` after registering,
+    /// to avoid missing a wakeup. The default implementation wakes the
+    /// waker immediately, so the capture will be polled in a loop.
+    #[inline]
+    fn register_waker(&self, _: &mut Self::Capture, waker: &Waker) { waker.wake_by_ref() }
+
     fn begin_guard(&self, tag: &mut Self::ReaderTag) -> Self::RawGuard;
 
     unsafe fn end_guard(&self, guard: Self::RawGuard);
 }
 
+/// A `Strategy` that lets a reader hold multiple guards at once
+pub unsafe trait SharedStrategy: Strategy {
+    fn begin_shared_guard(&self, tag: &Self::ReaderTag) -> Self::RawGuard;
+}
+
+/// How a `Strategy` waited in `pause`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseKind {
+    /// Spun or yielded, without blocking the thread
+    Spin,
+    /// Blocked the thread until woken or timed out
+    Park,
+}
+
 pub unsafe trait RawDoubleBuffer {
     type Buffer: ?Sized;
 
     unsafe fn split(this: *mut Self, which: bool) -> (*mut Self::Buffer, *const Self::Buffer);
 }
 
+pub unsafe trait RawTripleBuffer {
+    type Buffer: ?Sized;
+
+    unsafe fn get(this: *mut Self, index: usize) -> *mut Self::Buffer;
+}
+
 pub unsafe trait TrustedRadium: Radium {
     #[doc(hidden)]
     unsafe fn load_unsync(&self) -> Self::Item;
@@ -102,3 +154,20 @@ pub trait Operation<B: ?Sized>: Sized {
     fn apply(&mut self, buffer: &mut B);
     fn apply_final(mut self, buffer: &mut B) { self.apply(buffer) }
 }
+
+/// An `Operation` that also maintains writer-only state, such as indexes that readers never use
+///
+/// Each operation is applied to both buffers, but there is only one `extra`,
+/// so it should only be updated in `apply`, not in `apply_final`
+pub trait OperationWith<B: ?Sized, X: ?Sized>: Sized {
+    fn apply(&mut self, buffer: &mut B, extra: &mut X);
+    fn apply_final(mut self, buffer: &mut B, extra: &mut X) { OperationWith::apply(&mut self, buffer, extra) }
+}
+
+impl<B: ?Sized, O: Operation<B>> OperationWith<B, ()> for O {
+    #[inline]
+    fn apply(&mut self, buffer: &mut B, (): &mut ()) { Operation::apply(self, buffer) }
+
+    #[inline]
+    fn apply_final(self, buffer: &mut B, (): &mut ()) { Operation::apply_final(self, buffer) }
+}
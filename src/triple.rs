@@ -0,0 +1,116 @@
+//! A single reader triple buffer, where the writer never waits on the reader
+//!
+//! The writer and reader each own one buffer, and the third holds the latest
+//! published value, which both sides exchange their own buffer with.
+
+use core::{
+    cell::UnsafeCell,
+    ops::Deref,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use crate::traits::RawTripleBuffer;
+
+const INDEX: u8 = 0b011;
+const DIRTY: u8 = 0b100;
+
+const WRITER: u8 = 0;
+const MIDDLE: u8 = 1;
+const READER: u8 = 2;
+
+#[cfg(feature = "alloc")]
+type Shared<B> = std::sync::Arc<Inner<[B; 3]>>;
+
+#[cfg(feature = "alloc")]
+pub fn new<B: Default>() -> (Writer<Shared<B>>, Reader<Shared<B>>) {
+    from_buffers(B::default(), B::default(), B::default())
+}
+
+#[cfg(feature = "alloc")]
+pub fn from_buffers<B>(writer: B, middle: B, reader: B) -> (Writer<Shared<B>>, Reader<Shared<B>>) {
+    let inner = std::sync::Arc::new(Inner::new(writer, middle, reader));
+    unsafe { (Writer::from_raw_parts(inner.clone()), Reader::from_raw_parts(inner)) }
+}
+
+pub struct Inner<R: ?Sized> {
+    state: AtomicU8,
+    raw: UnsafeCell<R>,
+}
+
+unsafe impl<R: ?Sized + Send> Send for Inner<R> {}
+unsafe impl<R: ?Sized + Send + Sync> Sync for Inner<R> {}
+
+pub struct Writer<P> {
+    index: u8,
+    inner: P,
+}
+
+pub struct Reader<P> {
+    index: u8,
+    inner: P,
+}
+
+impl<B> Inner<[B; 3]> {
+    pub const fn new(writer: B, middle: B, reader: B) -> Self { Self::from_raw_parts([writer, middle, reader]) }
+}
+
+impl<R> Inner<R> {
+    pub const fn from_raw_parts(buffers: R) -> Self {
+        Self {
+            state: AtomicU8::new(MIDDLE),
+            raw: UnsafeCell::new(buffers),
+        }
+    }
+}
+
+impl<R: ?Sized + RawTripleBuffer> Inner<R> {
+    pub fn split(&mut self) -> (Writer<&Self>, Reader<&Self>) {
+        *self.state.get_mut() = MIDDLE;
+        let this = &*self;
+        unsafe { (Writer::from_raw_parts(this), Reader::from_raw_parts(this)) }
+    }
+
+    unsafe fn get(&self, index: u8) -> *mut R::Buffer { RawTripleBuffer::get(self.raw.get(), usize::from(index)) }
+}
+
+impl<P> Writer<P> {
+    unsafe fn from_raw_parts(inner: P) -> Self { Self { index: WRITER, inner } }
+}
+
+impl<P> Reader<P> {
+    unsafe fn from_raw_parts(inner: P) -> Self { Self { index: READER, inner } }
+}
+
+impl<R: ?Sized + RawTripleBuffer, P: Deref<Target = Inner<R>>> Writer<P> {
+    pub fn get(&self) -> &R::Buffer { unsafe { &*self.inner.get(self.index) } }
+
+    pub fn get_mut(&mut self) -> &mut R::Buffer { unsafe { &mut *self.inner.get(self.index) } }
+
+    pub fn swap_buffers(&mut self) {
+        // `Release` publishes the writes to the current buffer,
+        // `Acquire` syncronizes with the reader giving up it's old buffer
+        let state = self.inner.state.swap(self.index | DIRTY, Ordering::AcqRel);
+        self.index = state & INDEX;
+    }
+}
+
+impl<R: ?Sized + RawTripleBuffer, P: Deref<Target = Inner<R>>> Reader<P> {
+    pub fn has_update(&self) -> bool { self.inner.state.load(Ordering::Relaxed) & DIRTY != 0 }
+
+    pub fn get(&mut self) -> &R::Buffer {
+        if self.has_update() {
+            let state = self.inner.state.swap(self.index, Ordering::AcqRel);
+            self.index = state & INDEX;
+        }
+
+        unsafe { &*self.inner.get(self.index) }
+    }
+}
+
+impl<R: ?Sized + RawTripleBuffer, P: Deref<Target = Inner<R>>> AsRef<R::Buffer> for Writer<P> {
+    fn as_ref(&self) -> &R::Buffer { self.get() }
+}
+
+impl<R: ?Sized + RawTripleBuffer, P: Deref<Target = Inner<R>>> AsMut<R::Buffer> for Writer<P> {
+    fn as_mut(&mut self) -> &mut R::Buffer { self.get_mut() }
+}
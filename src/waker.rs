@@ -0,0 +1,73 @@
+use core::{
+    sync::atomic::{fence, AtomicBool, Ordering},
+    task::Waker,
+};
+
+#[cfg(feature = "std")]
+use parking_lot::{lock_api::RawMutex as _, Mutex};
+#[cfg(not(feature = "std"))]
+use spin::Mutex;
+
+pub(crate) struct WakerSlot {
+    registered: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl Default for WakerSlot {
+    fn default() -> Self { Self::new() }
+}
+
+impl WakerSlot {
+    #[cfg(feature = "std")]
+    pub(crate) const fn new() -> Self {
+        Self {
+            registered: AtomicBool::new(false),
+            waker: Mutex::const_new(parking_lot::RawMutex::INIT, None),
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub(crate) const fn new() -> Self {
+        Self {
+            registered: AtomicBool::new(false),
+            waker: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn register(&self, waker: &Waker) {
+        {
+            let mut slot = self.waker.lock();
+            match &*slot {
+                Some(old) if old.will_wake(waker) => (),
+                _ => *slot = Some(waker.clone()),
+            }
+        }
+
+        self.registered.store(true, Ordering::Relaxed);
+
+        // pairs with the load in `wake`, either the reader sees the
+        // waker, or the writer sees that the reader has exited
+        fence(Ordering::SeqCst);
+    }
+
+    // the reader must have exited with a `SeqCst` RMW, so that
+    // it doesn't need a fence unless a waker was registered
+    #[inline]
+    pub(crate) fn wake(&self) {
+        if self.registered.load(Ordering::SeqCst) {
+            self.wake_slow()
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn wake_slow(&self) {
+        if self.registered.swap(false, Ordering::Acquire) {
+            let waker = self.waker.lock().take();
+
+            if let Some(waker) = waker {
+                waker.wake()
+            }
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use core::convert::TryFrom;
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    vec::Vec,
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    base::Writer,
+    deferred::WaitingStrategy,
+    op::OpWriter,
+    traits::{Buffer, Operation, StrongBuffer},
+};
+
+const MAGIC: &[u8; 8] = b"DBUFWAL\x01";
+
+// length (u32) + kind (u8) + payload checksum (u32) + checksum of the rest of the header (u32)
+const HEADER_LEN: usize = 13;
+
+// how many bytes of records are buffered before they are written to the file
+const BUFFER_LEN: usize = 8 * 1024;
+
+const OPERATION: u8 = 0;
+const SNAPSHOT: u8 = 1;
+
+pub struct WalWriter<I: StrongBuffer, O> {
+    writer: OpWriter<I, O>,
+    file: LogFile,
+    path: PathBuf,
+    truncated: u64,
+}
+
+struct LogFile {
+    file: File,
+    // records that haven't been written to the file yet
+    buffer: Vec<u8>,
+    // where the last record that was written in full ends
+    len: u64,
+}
+
+struct Log<O> {
+    snapshot: Option<Vec<u8>>,
+    operations: Vec<O>,
+    // the length of the valid prefix of the file
+    len: u64,
+}
+
+fn invalid_data<E: Into<std::boxed::Box<dyn std::error::Error + Send + Sync>>>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+fn read_u32(bytes: &[u8]) -> u32 { u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) }
+
+fn write_record<W: Write>(file: &mut W, kind: u8, payload: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(payload.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "wal record is too large"))?;
+
+    let mut header = [0; HEADER_LEN];
+    header[..4].copy_from_slice(&len.to_le_bytes());
+    header[4] = kind;
+    header[5..9].copy_from_slice(&crc32fast::hash(payload).to_le_bytes());
+    let checksum = crc32fast::hash(&header[..9]);
+    header[9..].copy_from_slice(&checksum.to_le_bytes());
+
+    file.write_all(&header)?;
+    file.write_all(payload)
+}
+
+fn parse_log<O: DeserializeOwned>(data: &[u8]) -> io::Result<Log<O>> {
+    let mut log = Log {
+        snapshot: None,
+        operations: Vec::new(),
+        len: 0,
+    };
+
+    // a crash while writing the header of a fresh log
+    if data.len() < MAGIC.len() && MAGIC.starts_with(data) {
+        return Ok(log)
+    }
+
+    if !data.starts_with(MAGIC) {
+        return Err(invalid_data("not a write-ahead log"))
+    }
+
+    let mut offset = MAGIC.len();
+    log.len = offset as u64;
+
+    while offset < data.len() {
+        let rest = &data[offset..];
+
+        // a crash while writing the header of the last record
+        if rest.len() < HEADER_LEN {
+            break
+        }
+
+        let (header, rest) = rest.split_at(HEADER_LEN);
+
+        if crc32fast::hash(&header[..9]) != read_u32(&header[9..]) {
+            return Err(invalid_data("wal record header checksum mismatch"))
+        }
+
+        let len = read_u32(header) as usize;
+        let kind = header[4];
+
+        // the length is known to be good, so the record really runs past the end of the file
+        let payload = match rest.get(..len) {
+            Some(payload) => payload,
+            None => break,
+        };
+
+        let end = offset + HEADER_LEN + len;
+
+        if crc32fast::hash(payload) != read_u32(&header[5..]) {
+            // only the last record may have been torn by a crash
+            if end == data.len() {
+                break
+            }
+
+            return Err(invalid_data("wal record checksum mismatch"))
+        }
+
+        match kind {
+            OPERATION => log.operations.push(bincode::deserialize(payload).map_err(invalid_data)?),
+            SNAPSHOT if offset == MAGIC.len() => log.snapshot = Some(payload.to_vec()),
+            _ => return Err(invalid_data("unexpected wal record")),
+        }
+
+        offset = end;
+        log.len = offset as u64;
+    }
+
+    Ok(log)
+}
+
+impl LogFile {
+    fn new(mut file: File) -> io::Result<Self> {
+        let len = file.seek(SeekFrom::End(0))?;
+
+        Ok(Self {
+            file,
+            buffer: Vec::new(),
+            len,
+        })
+    }
+
+    fn push(&mut self, kind: u8, payload: &[u8]) -> io::Result<()> {
+        let start = self.buffer.len();
+
+        let result = write_record(&mut self.buffer, kind, payload).and_then(|()| {
+            if self.buffer.len() < BUFFER_LEN {
+                Ok(())
+            } else {
+                self.flush()
+            }
+        });
+
+        if result.is_err() {
+            self.buffer.truncate(start);
+        }
+
+        result
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if let Err(error) = self.file.write_all(&self.buffer) {
+            // drop whatever made it to the file, so that the log doesn't end in a torn record
+            self.file.set_len(self.len)?;
+            self.file.seek(SeekFrom::Start(self.len))?;
+            return Err(error)
+        }
+
+        self.len += self.buffer.len() as u64;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    fn sync(&mut self) -> io::Result<()> {
+        self.flush()?;
+        self.file.sync_data()
+    }
+}
+
+impl Drop for LogFile {
+    fn drop(&mut self) { let _ = self.flush(); }
+}
+
+impl<I, O> WalWriter<I, O>
+where
+    I: StrongBuffer,
+    I::Strategy: WaitingStrategy,
+    O: Operation<Buffer<I>> + Serialize + DeserializeOwned,
+    Buffer<I>: Sized + DeserializeOwned,
+{
+    pub fn open<P: AsRef<Path>>(path: P, mut writer: Writer<I>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(&path)?;
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+
+        let log = parse_log::<O>(&data)?;
+        let truncated = data.len() as u64 - log.len;
+
+        if log.len == 0 {
+            file.set_len(0)?;
+            file.seek(SeekFrom::Start(0))?;
+            file.write_all(MAGIC)?;
+            file.sync_data()?;
+        } else if truncated != 0 {
+            file.set_len(log.len)?;
+            file.sync_data()?;
+        }
+
+        let file = LogFile::new(file)?;
+
+        if let Some(snapshot) = log.snapshot {
+            *writer.get_mut() = bincode::deserialize(&snapshot).map_err(invalid_data)?;
+            writer.swap_buffers();
+            *writer.get_mut() = bincode::deserialize(&snapshot).map_err(invalid_data)?;
+        }
+
+        let mut writer = OpWriter::from(writer);
+
+        if !log.operations.is_empty() {
+            // the first swap applies the log to one buffer, the second to the other
+            writer.extend(log.operations);
+            writer.swap_buffers();
+            writer.swap_buffers();
+        }
+
+        Ok(Self {
+            writer,
+            file,
+            path,
+            truncated,
+        })
+    }
+
+    pub fn push(&mut self, op: O) -> io::Result<()> {
+        let payload = bincode::serialize(&op).map_err(invalid_data)?;
+        self.file.push(OPERATION, &payload)?;
+        self.writer.push(op);
+        Ok(())
+    }
+
+    pub fn sync(&mut self) -> io::Result<()> { self.file.sync() }
+
+    pub fn swap_buffers(&mut self) -> io::Result<()> {
+        self.sync()?;
+        self.writer.swap_buffers();
+        Ok(())
+    }
+
+    pub fn checkpoint(&mut self) -> io::Result<()>
+    where
+        Buffer<I>: Serialize,
+    {
+        self.swap_buffers()?;
+
+        // the reader buffer has every logged operation applied to it
+        let snapshot = bincode::serialize(self.writer.split().reader).map_err(invalid_data)?;
+
+        let tmp = self.path.with_extension("checkpoint");
+        let mut file = BufWriter::new(File::create(&tmp)?);
+        file.write_all(MAGIC)?;
+        write_record(&mut file, SNAPSHOT, &snapshot)?;
+        let file = file.into_inner().map_err(io::IntoInnerError::into_error)?;
+        file.sync_all()?;
+
+        fs::rename(&tmp, &self.path)?;
+
+        #[cfg(unix)]
+        if let Some(parent) = self.path.parent() {
+            let parent = if parent.as_os_str().is_empty() { Path::new(".") } else { parent };
+            File::open(parent)?.sync_all()?;
+        }
+
+        self.file = LogFile::new(file)?;
+        Ok(())
+    }
+
+    pub fn truncated_bytes(&self) -> u64 { self.truncated }
+
+    pub fn path(&self) -> &Path { &self.path }
+
+    pub fn into_inner(mut self) -> io::Result<OpWriter<I, O>> {
+        self.sync()?;
+        Ok(self.writer)
+    }
+}
+
+impl<I: StrongBuffer, O> core::ops::Deref for WalWriter<I, O> {
+    type Target = Writer<I>;
+
+    fn deref(&self) -> &Self::Target { &self.writer }
+}
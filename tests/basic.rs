@@ -75,3 +75,871 @@ fn hazard() {
     drop(_a);
     w.swap_buffers();
 }
+
+#[test]
+#[cfg(feature = "std")]
+fn async_swap() {
+    use std::{
+        future::Future,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        task::{Context, Wake, Waker},
+    };
+
+    struct Flag(AtomicBool);
+
+    impl Wake for Flag {
+        fn wake(self: Arc<Self>) { self.0.store(true, Ordering::SeqCst) }
+    }
+
+    let flag = Arc::new(Flag(AtomicBool::new(false)));
+    let waker = Waker::from(flag.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    let (w, mut r) = saving::from_buffers(0, 1);
+    let mut w = double_buffer::deferred::DeferredWriter::from(w);
+    let guard = r.get();
+    w.start_swap();
+    let mut swap = Box::pin(w.finish_swap_async());
+
+    assert!(swap.as_mut().poll(&mut cx).is_pending());
+    assert!(!flag.0.load(Ordering::SeqCst));
+    drop(guard);
+    assert!(flag.0.load(Ordering::SeqCst));
+    assert!(swap.as_mut().poll(&mut cx).is_ready());
+    drop(swap);
+
+    assert_eq!(*w.get(), 1);
+    assert_eq!(*r.get(), 0);
+
+    // cancelling a swap doesn't block new readers
+    let (w, mut r) = sync::from_buffers(0, 1);
+    let mut r2 = r.clone();
+    let guard = r.get();
+    let mut swap = Box::pin(w.swap_buffers_async());
+    assert!(swap.as_mut().poll(&mut cx).is_pending());
+    drop(swap);
+    assert_eq!(*r2.get(), 0);
+    drop(guard);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn swap_deadline() {
+    use std::time::{Duration, Instant};
+
+    let (w, mut r) = sync::from_buffers(0, 1);
+    let mut w = double_buffer::deferred::DeferredWriter::new_unchecked(w);
+    let guard = r.get();
+    w.start_swap();
+
+    assert!(w.finish_swap_deadline(Instant::now() + Duration::from_millis(1)).is_none());
+    drop(guard);
+    assert!(w.finish_swap_deadline(Instant::now() + Duration::from_millis(1)).is_some());
+    assert_eq!(*r.get(), 0);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn triple_buffer() {
+    let (mut w, mut r) = double_buffer::triple::from_buffers(0, 0, 0);
+    assert_eq!(*r.get(), 0);
+    *w.get_mut() = 1;
+    w.swap_buffers();
+    *w.get_mut() = 2;
+    w.swap_buffers();
+    assert!(r.has_update());
+    assert_eq!(*r.get(), 2);
+    assert!(!r.has_update());
+    *w.get_mut() = 3;
+    w.swap_buffers();
+    *w.get_mut() = 4;
+    assert_eq!(*r.get(), 3);
+    assert_eq!(*r.get(), 3);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn epoch() {
+    let (mut w, mut r) = epoch::from_buffers(0, 1);
+    let mut r2 = r.clone();
+    let a = r.get();
+    let mut swap = unsafe { w.start_buffer_swap() };
+    assert!(!w.is_swap_complete(&mut swap));
+    assert_eq!(*r2.get(), 0);
+    drop(a);
+    assert!(w.is_swap_complete(&mut swap));
+    w.finish_buffer_swap(swap);
+    assert_eq!(*r.get(), 0);
+    w.swap_buffers();
+    assert_eq!(*r.get(), 1);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn sharded() {
+    let (mut w, mut r) = sharded::from_buffers::<_, 4>(0, 1);
+    let mut readers = [r.clone(), r.clone(), r.clone()];
+    let guards = readers.iter_mut().map(|r| r.get()).collect::<Vec<_>>();
+    assert!(w.try_swap_buffers().is_err());
+    drop(guards);
+    w.swap_buffers();
+    assert_eq!(*r.get(), 0);
+
+    // readers that start after the swap don't hold it up
+    let mut swap = unsafe { w.start_buffer_swap() };
+    let guard = readers[0].get();
+    assert!(w.is_swap_complete(&mut swap));
+    w.finish_buffer_swap(swap);
+    assert_eq!(*guard, 1);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn seqlock_load() {
+    let (mut w, r) = seqlock::from_buffers((0, 0), (1, 1));
+    assert_eq!(r.load(), (1, 1));
+    *w.get_mut() = (2, 2);
+    w.swap_buffers();
+    assert_eq!(r.load(), (2, 2));
+    *w.get_mut() = (3, 3);
+    assert_eq!(r.load(), (2, 2));
+
+    // guards that start after a swap don't hold it up
+    let (mut r, mut r2) = (r.clone(), r);
+    let a = r.get();
+    let mut swap = unsafe { w.start_buffer_swap() };
+    assert!(!w.is_swap_complete(&mut swap));
+    let b = r2.get();
+    drop(a);
+    assert!(w.is_swap_complete(&mut swap));
+    w.finish_buffer_swap(swap);
+    assert_eq!(*b, (3, 3));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn collections_map() {
+    let (mut w, r) = double_buffer::collections::map::new();
+    w.insert("a", 1);
+    w.insert("b", 2);
+    assert!(r.is_empty());
+    w.publish();
+    assert_eq!(r.get("a").as_deref(), Some(&1));
+    w.remove("a");
+    w.insert("c", 3);
+    w.publish();
+    assert!(!r.contains_key("a"));
+    assert_eq!(r.len(), 2);
+    let mut items = r.iter().collect::<Vec<_>>();
+    items.sort();
+    assert_eq!(items, [("b", 2), ("c", 3)]);
+    w.clear();
+    w.publish();
+    w.publish();
+    assert!(r.is_empty());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn collections_vec() {
+    let (mut w, r) = double_buffer::collections::vec::new();
+    w.push(1);
+    w.push(2);
+    w.push(3);
+    w.publish();
+    assert_eq!(*r.read(), [1, 2, 3]);
+    w.insert(0, 0);
+    w.swap_remove(1);
+    w.remove(1);
+    w.publish();
+    assert_eq!(r.get(0).as_deref(), Some(&0));
+    assert_eq!(r.iter().collect::<Vec<_>>(), [0, 2]);
+    w.truncate(1);
+    w.publish();
+    w.publish();
+    assert_eq!(*r.read(), [0]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+#[should_panic(expected = "removal index (is 2) should be < len (is 2)")]
+fn collections_vec_out_of_bounds() {
+    let (mut w, _r) = double_buffer::collections::vec::new();
+    w.push(1);
+    w.publish();
+    w.push(2);
+    w.remove(2);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn fn_op_writer() {
+    use double_buffer::op::{FnOp, OpWriter};
+
+    let (w, mut r) = saving::from_buffers(Vec::new(), Vec::new());
+    let mut w = OpWriter::from(w);
+    w.push_fn(|v: &mut Vec<i32>| v.push(1));
+    let value = String::from("2");
+    w.push(FnOp::with_final(
+        {
+            let value = value.clone();
+            move |v: &mut Vec<i32>| v.push(value.parse().unwrap())
+        },
+        move |v| v.push(value.parse().unwrap()),
+    ));
+    w.swap_buffers();
+    assert_eq!(*r.get(), [1, 2]);
+    w.swap_buffers();
+    assert_eq!(*r.get(), [1, 2]);
+    assert_eq!(*w.get(), [1, 2]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn thin_weak() {
+    use double_buffer::{athin::AthinInner, thin::ThinInner};
+    use std::boxed::Box;
+
+    let inner = Box::new(AthinInner::new(Inner::new(saving::SavingStrategy::default(), 0, 1)));
+    let (mut w, mut r) = base::new(inner);
+    w.swap_buffers();
+    assert_eq!(*r.get(), 0);
+    let r2 = r.clone();
+    drop(w);
+    assert!(r.is_dangling());
+    assert!(r.try_get().is_err());
+    drop((r, r2));
+
+    let inner = Box::new(ThinInner::new(Inner::new(local::LocalStrategy::default(), 0, 1)));
+    let (w, mut r) = base::new(inner);
+    assert_eq!(*r.get(), 1);
+    drop(w);
+    assert!(r.is_dangling());
+    assert!(r.try_get().is_err());
+
+    // an inner that's dropped before it's shared still drops its value
+    let value = std::sync::Arc::new(());
+    drop(Box::new(AthinInner::new(value.clone())));
+    drop(Box::new(ThinInner::new(value.clone())));
+    assert_eq!(std::sync::Arc::strong_count(&value), 1);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn wait_for_swap() {
+    use std::time::Duration;
+
+    let (mut w, mut r) = sync::new::<i32>();
+    assert_eq!(r.generation(), 0);
+    assert!(!r.has_changed());
+    assert!(!r.wait_for_swap(Duration::from_millis(1)));
+
+    let reader = r.clone();
+    let waiter = std::thread::spawn(move || reader.wait_for_swap(Duration::from_secs(10)));
+    std::thread::sleep(Duration::from_millis(10));
+    *w.get_mut() = 1;
+    w.swap_buffers();
+    assert!(waiter.join().unwrap());
+
+    assert_eq!(w.generation(), 1);
+    assert!(r.has_changed());
+    assert_eq!(*r.get(), 1);
+    assert!(!r.has_changed());
+}
+
+#[test]
+#[cfg(feature = "futures")]
+fn reader_changes() {
+    use futures_core::Stream;
+    use std::{
+        pin::Pin,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        task::{Context, Poll, Wake, Waker},
+    };
+
+    struct Flag(AtomicBool);
+
+    impl Wake for Flag {
+        fn wake(self: Arc<Self>) { self.0.store(true, Ordering::SeqCst) }
+    }
+
+    let flag = Arc::new(Flag(AtomicBool::new(false)));
+    let waker = Waker::from(flag.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    let (mut w, mut r) = saving::from_buffers(0, 1);
+    let mut changes = r.changes();
+
+    assert_eq!(Pin::new(&mut changes).poll_next(&mut cx), Poll::Pending);
+    *w.get_mut() = 2;
+    w.swap_buffers();
+    assert!(flag.0.swap(false, Ordering::SeqCst));
+    assert_eq!(Pin::new(&mut changes).poll_next(&mut cx), Poll::Ready(Some(2)));
+    assert_eq!(Pin::new(&mut changes).poll_next(&mut cx), Poll::Pending);
+
+    // swaps before the next poll are merged into the latest snapshot
+    *w.get_mut() = 3;
+    w.swap_buffers();
+    *w.get_mut() = 4;
+    w.swap_buffers();
+    assert_eq!(Pin::new(&mut changes).poll_next(&mut cx), Poll::Ready(Some(4)));
+    assert_eq!(Pin::new(&mut changes).poll_next(&mut cx), Poll::Pending);
+
+    drop(w);
+    assert!(flag.0.load(Ordering::SeqCst));
+    assert_eq!(Pin::new(&mut changes).poll_next(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn replicate_op_log() {
+    use double_buffer::{op::OpWriter, op_list::OpList};
+
+    #[derive(Clone, serde::Serialize, serde::Deserialize)]
+    struct Add(i32);
+
+    impl double_buffer::traits::Operation<i32> for Add {
+        fn apply(&mut self, buffer: &mut i32) { *buffer += self.0 }
+    }
+
+    let (leader, mut leader_reader) = saving::from_buffers(0, 0);
+    let mut leader = OpWriter::<_, Add>::from(leader);
+    let (follower, mut follower_reader) = saving::from_buffers(0, 0);
+    let mut follower = OpWriter::<_, Add>::from(follower);
+
+    for batch in [&[1, 2][..], &[3], &[]] {
+        leader.extend(batch.iter().copied().map(Add));
+        let log = serde_json::to_string(&leader.drain_pending_log()).unwrap();
+        assert!(leader.drain_pending_log().is_empty());
+        leader.swap_buffers();
+
+        follower.apply_log(serde_json::from_str::<OpList<Add>>(&log).unwrap());
+        assert_eq!(*follower_reader.get(), *leader_reader.get());
+    }
+
+    assert_eq!(*follower_reader.get(), 6);
+
+    // operations removed by a second swap are still logged
+    leader.push(Add(4));
+    leader.swap_buffers();
+    leader.swap_buffers();
+    follower.apply_log(leader.drain_pending_log());
+    assert_eq!(*follower_reader.get(), 10);
+    assert_eq!(*leader_reader.get(), 10);
+}
+
+#[test]
+#[cfg(feature = "wal")]
+fn write_ahead_log() {
+    use double_buffer::wal::WalWriter;
+    use std::io::Write;
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Add(i32);
+
+    impl double_buffer::traits::Operation<i32> for Add {
+        fn apply(&mut self, buffer: &mut i32) { *buffer += self.0 }
+    }
+
+    let path = std::env::temp_dir().join(format!("double-buffer-wal-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let open = |path: &std::path::Path| {
+        let (w, r) = saving::from_buffers(0, 0);
+        (WalWriter::<_, Add>::open(path, w).unwrap(), r)
+    };
+
+    let (mut wal, mut r) = open(&path);
+    wal.push(Add(1)).unwrap();
+    wal.push(Add(2)).unwrap();
+    wal.swap_buffers().unwrap();
+    assert_eq!(*r.get(), 3);
+    drop(wal);
+
+    // a torn record at the end of the log
+    std::fs::OpenOptions::new().append(true).open(&path).unwrap().write_all(&[4, 0, 0]).unwrap();
+
+    let (mut wal, mut r) = open(&path);
+    assert_eq!(wal.truncated_bytes(), 3);
+    assert_eq!((*r.get(), *wal.get()), (3, 3));
+    wal.push(Add(4)).unwrap();
+    wal.checkpoint().unwrap();
+    wal.push(Add(10)).unwrap();
+    drop(wal);
+
+    let (wal, mut r) = open(&path);
+    assert_eq!(wal.truncated_bytes(), 0);
+    assert_eq!((*r.get(), *wal.get()), (17, 17));
+    drop(wal);
+
+    // a bad length in a record that isn't the last one
+    let mut data = std::fs::read(&path).unwrap();
+    data[8] ^= 1;
+    std::fs::write(&path, data).unwrap();
+
+    let (w, _r) = saving::from_buffers(0, 0);
+    let error = WalWriter::<_, Add>::open(&path, w).err().unwrap();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn snapshot_restore() {
+    use std::sync::Arc;
+
+    let (mut w, _r) = saving::from_buffers(vec![0], vec![1]);
+    w.get_mut().push(2);
+    let json = serde_json::to_string(&w.snapshot()).unwrap();
+
+    let (w, mut r) = saving::from_snapshot::<Vec<i32>>(serde_json::from_str(&json).unwrap());
+    assert_eq!(*w.get(), [0, 2]);
+    assert_eq!(*r.get(), [0, 2]);
+
+    let inner = Inner::<[String], _>::from_slice_snapshot(saving::SavingStrategy::default(), &["a".into(), "b".into()]);
+    let (mut w, mut r) = base::new(Arc::<Inner<[String], _>>::from(inner));
+    w.get_mut()[0] = "c".into();
+    w.swap_buffers();
+    assert_eq!(*r.get(), ["c", "b"]);
+    assert_eq!(*w.get(), ["a", "b"]);
+
+    let json = serde_json::to_string(&w.snapshot()).unwrap();
+    let snapshot: Vec<String> = serde_json::from_str(&json).unwrap();
+    let inner = Inner::<[String], _>::from_slice_snapshot(saving::SavingStrategy::default(), &snapshot);
+    let (w, mut r) = base::new(Arc::<Inner<[String], _>>::from(inner));
+    assert_eq!(w.snapshot(), ["a", "b"]);
+    assert_eq!(*r.get(), ["a", "b"]);
+}
+
+#[test]
+#[cfg(feature = "metrics")]
+fn strategy_stats() {
+    use double_buffer::strategy::stats::StrategyStats;
+    use std::sync::Arc;
+
+    let inner = Inner::new(StrategyStats::new(saving_park::SavingParkStrategy::default()), 0, 1);
+    let (mut w, mut r) = base::new(Arc::new(inner));
+    w.swap_buffers();
+
+    let guard = r.get();
+    std::thread::scope(|s| {
+        s.spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            drop(guard);
+        });
+        w.swap_buffers();
+    });
+
+    // time between starting and finishing a swap without any readers isn't a wait
+    let swap = unsafe { w.start_buffer_swap() };
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    w.finish_buffer_swap(swap);
+
+    let stats = w.strategy().stats();
+    assert_eq!(stats.swaps, 3);
+    assert_eq!(stats.total_waits(), 3);
+    assert_eq!(stats.wait_histogram[0], 2);
+    assert_eq!(stats.max_active_readers, 1);
+    assert!(stats.spins + stats.parks > 0);
+
+    // nor is the time after the readers have exited
+    let guard = r.get();
+    let mut swap = unsafe { w.start_buffer_swap() };
+    assert!(!w.is_swap_complete(&mut swap));
+    drop(guard);
+    assert!(w.is_swap_complete(&mut swap));
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    w.finish_buffer_swap(swap);
+
+    // only the swap that waited on the sleeping thread took 4ms or more
+    let stats = w.strategy().stats();
+    assert_eq!(stats.total_waits(), 4);
+    assert_eq!(stats.wait_histogram[12..].iter().sum::<u64>(), 1);
+}
+
+#[test]
+#[cfg(feature = "tracing")]
+fn tracing_events() {
+    use std::sync::{Arc, Mutex};
+    use tracing_crate::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
+
+    #[derive(Default)]
+    struct Collect(Mutex<Vec<String>>, Mutex<Vec<String>>);
+
+    struct Message<'a>(&'a mut String, &'a mut Option<String>);
+
+    impl Visit for Message<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            match field.name() {
+                "message" => *self.0 = format!("{:?}", value),
+                "reader" => *self.1 = Some(format!("{:?}", value)),
+                _ => (),
+            }
+        }
+    }
+
+    impl Subscriber for Collect {
+        fn enabled(&self, _: &Metadata<'_>) -> bool { true }
+
+        fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+            self.0.lock().unwrap().push(span.metadata().name().to_string());
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut message = String::new();
+            let mut reader = None;
+            event.record(&mut Message(&mut message, &mut reader));
+            self.0.lock().unwrap().push(message);
+            self.1.lock().unwrap().extend(reader);
+        }
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    let collect = Arc::new(Collect::default());
+
+    tracing_crate::subscriber::with_default(collect.clone(), || {
+        let (mut w, mut r) = saving::from_buffers(0, 1);
+        drop(r.get());
+        w.swap_buffers();
+    });
+
+    let log = collect.0.lock().unwrap();
+    assert_eq!(
+        *log,
+        [
+            "reader guard acquired",
+            "reader guard released",
+            "try_start_buffer_swap",
+            "started buffer swap",
+            "finish_buffer_swap",
+        ]
+    );
+    drop(log);
+
+    // the writer parks many times while the guard is held, but only logs it once
+    let collect = Arc::new(Collect::default());
+
+    tracing_crate::subscriber::with_default(collect.clone(), || {
+        let (mut w, mut r) = base::new(Arc::new(Inner::new(saving_park::SavingParkStrategy::default(), 0, 1)));
+        let guard = r.get();
+        let swap = unsafe { w.start_buffer_swap() };
+        let deadline = std::time::Instant::now() + std::time::Duration::from_millis(5);
+        let swap = w.finish_buffer_swap_deadline(swap, deadline).err().unwrap();
+        drop(guard);
+        w.finish_buffer_swap(swap);
+    });
+
+    let log = collect.0.lock().unwrap();
+    let count = |message: &str| log.iter().filter(|m| *m == message).count();
+    assert_eq!(count("writer waiting on reader"), 1);
+    assert_eq!(count("writer parked waiting for readers"), 1);
+
+    // the reader and the writer identify the reader the same way
+    let readers = collect.1.lock().unwrap();
+    assert_eq!(readers.len(), 3);
+    assert_eq!(readers[0], readers[2]);
+    assert_eq!(readers[0], format!("Some({})", readers[1]));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn leaked_reader_guard() {
+    use double_buffer::strategy::leak::{LeakAction, LeakPolicy};
+    use std::sync::{Arc, Mutex};
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let policy = |limit| {
+        let log = events.clone();
+        let policy = LeakPolicy::new()
+            .stall_limit(limit)
+            .on_leak(move |reader| log.lock().unwrap().push(reader.action));
+        saving::SavingStrategy::with_leak_policy(unsafe { policy.evict() })
+    };
+
+    // a reader that starts a new guard before each swap isn't stuck, no matter how often it's polled
+    let (mut w, mut r) = base::new(Arc::new(Inner::new(policy(2), 0, 1)));
+
+    for _ in 0..3 {
+        let guard = r.get();
+        let mut swap = unsafe { w.start_buffer_swap() };
+        for _ in 0..10 {
+            assert!(!w.is_swap_complete(&mut swap));
+        }
+        drop(guard);
+        w.finish_buffer_swap(swap);
+    }
+    assert!(events.lock().unwrap().is_empty());
+
+    let (mut w, mut r) = base::new(Arc::new(Inner::new(policy(1), 0, 1)));
+
+    core::mem::forget(r.get());
+    w.swap_buffers();
+    assert_eq!(*events.lock().unwrap(), [LeakAction::Evicted]);
+    assert_eq!(*r.get(), 0);
+
+    core::mem::forget(r.get());
+    assert_eq!(*r.get(), 0);
+    assert_eq!(*events.lock().unwrap(), [LeakAction::Evicted, LeakAction::Recovered]);
+
+    w.swap_buffers();
+    assert_eq!(*r.get(), 1);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn owned_reader_guard() {
+    use double_buffer::base::OwnedReaderGuard;
+
+    let (mut w, r) = saving::from_buffers(vec![1, 2, 3], vec![4, 5, 6]);
+
+    let guard = r.get_owned();
+    let guard = OwnedReaderGuard::map(guard, |buffer| &buffer[1..]);
+    let guard = OwnedReaderGuard::try_map(guard, |buffer| buffer.first()).ok().unwrap();
+    let guard = std::thread::spawn(move || {
+        assert_eq!(*guard, 5);
+        guard
+    })
+    .join()
+    .unwrap();
+
+    let mut r = OwnedReaderGuard::into_reader(guard);
+    w.swap_buffers();
+    assert_eq!(*r.get(), [1, 2, 3]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn shared_reader_guards() {
+    let (mut w, r) = saving::from_buffers(0, 1);
+
+    let a = r.get_shared();
+    let b = r.get_shared();
+    assert_eq!((*a, *b), (1, 1));
+
+    let mut swap = unsafe { w.try_start_buffer_swap() }.unwrap();
+    assert!(!w.is_swap_complete(&mut swap));
+    drop(a);
+    assert!(!w.is_swap_complete(&mut swap));
+    drop(b);
+    assert!(w.is_swap_complete(&mut swap));
+    w.finish_buffer_swap(swap);
+
+    let (mut w, mut r) = local_saving::from_buffers(0, 1);
+    let a = r.get_shared();
+    let b = r.get_shared();
+    drop((a, b));
+    w.swap_buffers();
+    assert_eq!(*r.get(), 0);
+    assert_eq!(*r.get_shared(), 0);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn shared_writer() {
+    use double_buffer::{
+        op::{FnOp, OpWriter},
+        shared::SharedWriter,
+    };
+
+    let (w, mut r) = saving::from_buffers(Vec::new(), Vec::new());
+    let w = SharedWriter::new(OpWriter::from(w));
+
+    std::thread::scope(|s| {
+        for i in 0..4 {
+            let w = w.clone();
+            s.spawn(move || {
+                for j in 0..25 {
+                    w.submit(FnOp::new(move |v: &mut Vec<i32>| v.push(i * 25 + j)));
+                }
+            });
+        }
+    });
+
+    w.flush();
+    assert_eq!(w.queued(), 0);
+
+    let mut values = r.get().clone();
+    values.sort_unstable();
+    assert_eq!(values, (0..100).collect::<Vec<_>>());
+    assert_eq!(w.try_into_inner().ok().unwrap().get().len(), values.len());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn publisher_thread() {
+    use double_buffer::{
+        op::{FnOp, OpWriter},
+        publisher::{PublishPolicy, Publisher},
+    };
+    use std::time::Duration;
+
+    let (w, mut r) = saving::from_buffers(Vec::new(), Vec::new());
+    let publisher = Publisher::spawn(OpWriter::from(w), PublishPolicy::new().batch_size(1000));
+
+    let handle = publisher.handle();
+    for i in 0..25 {
+        handle.push(FnOp::new(move |v: &mut Vec<i32>| v.push(i))).ok().unwrap();
+    }
+    publisher.flush().unwrap();
+    assert_eq!(*r.get(), (0..25).collect::<Vec<_>>());
+
+    let w = publisher.shutdown();
+    assert!(handle.flush().is_err());
+
+    let publisher = Publisher::spawn(w, PublishPolicy::new().interval(Duration::from_millis(1)));
+    publisher.push(FnOp::new(|v: &mut Vec<i32>| v.clear())).ok().unwrap();
+    assert!(r.wait_for_swap(Duration::from_secs(5)));
+    assert!(r.get().is_empty());
+    drop(publisher);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn op_writer_extra() {
+    use double_buffer::{op::OpWriter, traits::OperationWith};
+    use std::collections::HashMap;
+
+    struct Push(&'static str);
+
+    impl OperationWith<Vec<&'static str>, HashMap<&'static str, usize>> for Push {
+        fn apply(&mut self, buffer: &mut Vec<&'static str>, index: &mut HashMap<&'static str, usize>) {
+            index.insert(self.0, buffer.len());
+            buffer.push(self.0);
+        }
+
+        fn apply_final(self, buffer: &mut Vec<&'static str>, _: &mut HashMap<&'static str, usize>) {
+            buffer.push(self.0);
+        }
+    }
+
+    let (w, mut r) = saving::new();
+    let mut w = OpWriter::with_extra(w.into(), HashMap::new());
+    w.push(Push("a"));
+    w.push(Push("b"));
+    w.swap_buffers();
+    w.push(Push("c"));
+    w.swap_buffers();
+
+    assert_eq!(*r.get(), ["a", "b", "c"]);
+    assert_eq!(w.extra()["c"], 2);
+    assert_eq!(w.extra().len(), 3);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn slice_buffers() {
+    use double_buffer::{op::OpWriter, raw::Resize};
+
+    let (w, mut r) = saving::from_slices(vec![1, 2], vec![1, 2]);
+    let mut w = OpWriter::from(w);
+    w.push(Resize { len: 4, value: 0 });
+    w.swap_buffers();
+    assert_eq!(**r.get(), [1, 2, 0, 0]);
+    w.push(Resize { len: 1, value: 0 });
+    w.swap_buffers();
+    assert_eq!(**r.get(), [1]);
+    assert_eq!(**w.get(), [1, 2, 0, 0]);
+    w.swap_buffers();
+    assert_eq!(**w.get(), [1]);
+
+    let (mut w, mut r) = local::from_slices(vec![1, 2], vec![3, 4]);
+    w.get_mut()[0] = 5;
+    w.swap_buffers();
+    assert_eq!(**r.get(), [5, 2]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+#[should_panic(expected = "must have the same length")]
+fn slice_buffers_length_mismatch() { let _ = saving::from_slices(vec![1, 2], vec![1]); }
+
+#[test]
+#[cfg(feature = "alloc")]
+fn separate_halves() {
+    use double_buffer::raw::{Padded, Pair};
+    use std::sync::Arc;
+
+    let inner = Arc::new(Inner::from_raw_parts(saving::SavingStrategy::default(), Pair::boxed(0, 1)));
+    let (mut w, mut r) = base::new(inner);
+    *w.get_mut() = 2;
+    w.swap_buffers();
+    assert_eq!(*r.get(), 2);
+
+    let inner = Arc::new(Inner::from_raw_parts(
+        saving::SavingStrategy::default(),
+        Pair::new(Box::<[i32]>::from([1, 2]), Box::from([3])),
+    ));
+    let (mut w, mut r) = base::new(inner);
+    assert_eq!(*r.get(), [3]);
+    w.swap_buffers();
+    assert_eq!(*r.get(), [1, 2]);
+
+    let (mut front, mut back) = (0, 1);
+    let mut inner = Inner::from_raw_parts(local::LocalStrategy::default(), Pair::new(&mut front, &mut back));
+    let (mut w, mut r) = base::new(&mut inner);
+    *w.get_mut() = 3;
+    w.swap_buffers();
+    assert_eq!(*r.get(), 3);
+
+    let mut inner = Inner::from_raw_parts(local::LocalStrategy::default(), Padded::new(0u8, 1));
+    let (mut w, mut r) = base::new(&mut inner);
+    assert_eq!(*r.get(), 1);
+    *w.get_mut() = 4;
+    w.swap_buffers();
+    assert_eq!(*r.get(), 4);
+    assert_eq!(*w.get(), 1);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn concurrent_shared_reader_guards() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let (mut w, r) = saving::from_buffers(vec![0; 64], vec![0; 64]);
+    let done = AtomicBool::new(false);
+
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                while !done.load(Ordering::Relaxed) {
+                    let outer = r.get_shared();
+                    let first = outer[0];
+                    let inner = r.get_shared();
+                    drop(outer);
+                    std::hint::spin_loop();
+                    // the writer must not touch a buffer while any guard is reading it
+                    assert!(inner.iter().all(|&x| x == inner[0]));
+                    assert!(first <= inner[0]);
+                    drop(inner);
+                }
+            });
+        }
+
+        for i in 1..=200 {
+            for x in w.get_mut().iter_mut() {
+                *x = i;
+            }
+            w.swap_buffers();
+        }
+
+        done.store(true, Ordering::Relaxed);
+    });
+}